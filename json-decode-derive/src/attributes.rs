@@ -0,0 +1,115 @@
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// Attributes that can be placed on the struct or enum being derived.
+#[derive(Default)]
+pub struct ContainerAttributes {
+    pub tag: Option<String>,
+    pub content: Option<String>,
+}
+
+/// Attributes that can be placed on a field.
+#[derive(Default)]
+pub struct FieldAttributes {
+    pub rename: Option<String>,
+    pub default: bool,
+    pub with: Option<syn::ExprPath>,
+}
+
+/// Attributes that can be placed on an enum variant.
+#[derive(Default)]
+pub struct VariantAttributes {
+    pub rename: Option<String>,
+}
+
+impl ContainerAttributes {
+    pub fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = ContainerAttributes::default();
+        for_each_meta(attrs, |meta| match meta {
+            Meta::NameValue(nv) if nv.path.is_ident("tag") => {
+                result.tag = Some(string_value(&nv.lit)?);
+                Ok(())
+            }
+            Meta::NameValue(nv) if nv.path.is_ident("content") => {
+                result.content = Some(string_value(&nv.lit)?);
+                Ok(())
+            }
+            _ => Err(unknown_attribute(meta)),
+        })?;
+        Ok(result)
+    }
+}
+
+impl FieldAttributes {
+    pub fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = FieldAttributes::default();
+        for_each_meta(attrs, |meta| match meta {
+            Meta::NameValue(nv) if nv.path.is_ident("rename") => {
+                result.rename = Some(string_value(&nv.lit)?);
+                Ok(())
+            }
+            Meta::NameValue(nv) if nv.path.is_ident("with") => {
+                result.with = Some(syn::parse_str(&string_value(&nv.lit)?)?);
+                Ok(())
+            }
+            Meta::Path(path) if path.is_ident("default") => {
+                result.default = true;
+                Ok(())
+            }
+            _ => Err(unknown_attribute(meta)),
+        })?;
+        Ok(result)
+    }
+}
+
+impl VariantAttributes {
+    pub fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = VariantAttributes::default();
+        for_each_meta(attrs, |meta| match meta {
+            Meta::NameValue(nv) if nv.path.is_ident("rename") => {
+                result.rename = Some(string_value(&nv.lit)?);
+                Ok(())
+            }
+            _ => Err(unknown_attribute(meta)),
+        })?;
+        Ok(result)
+    }
+}
+
+/// Calls `func` with each item inside any `#[json_decode(...)]` attributes
+fn for_each_meta(
+    attrs: &[Attribute],
+    mut func: impl FnMut(&Meta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    for attr in attrs.iter().filter(|a| a.path.is_ident("json_decode")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => {
+                for nested in list.nested.iter() {
+                    match nested {
+                        NestedMeta::Meta(meta) => func(meta)?,
+                        NestedMeta::Lit(lit) => {
+                            return Err(syn::Error::new_spanned(lit, "expected an attribute"))
+                        }
+                    }
+                }
+            }
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "expected #[json_decode(...)]",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn string_value(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(s) => Ok(s.value()),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string")),
+    }
+}
+
+fn unknown_attribute(meta: &Meta) -> syn::Error {
+    syn::Error::new_spanned(meta, "unknown json_decode attribute")
+}
@@ -0,0 +1,277 @@
+//! A derive macro for [json-decode](https://docs.rs/json-decode).
+//!
+//! `#[derive(JsonDecode)]` implements `json_decode::Decode` for a struct or enum,
+//! using json-decode's decoder functions.
+//!
+//! The following attributes are supported:
+//!
+//! - `#[json_decode(tag = "type")]` on an enum decodes it as an internally
+//!   tagged union.  Adding `content = "..."` makes it adjacently tagged.  Enums
+//!   without a `tag` are externally tagged.  Unit variants can be written as a
+//!   bare string in externally tagged enums, and without the content field in
+//!   adjacently tagged ones.  Any content they do have must be `null`.  Tuple
+//!   variants without exactly one field need a `content` field if the enum has
+//!   a `tag`.
+//! - `#[json_decode(rename = "...")]` on a field or variant changes the name
+//!   used in the JSON.
+//! - `#[json_decode(default)]` on a field uses `Default::default()` if the field
+//!   is missing or `null`.
+//! - `#[json_decode(with = "path::to::fn")]` on a field decodes it with the
+//!   decoder returned by the given function.
+//!
+//! Unit structs are decoded from `null`, and structs with no named fields from
+//! any object.
+//!
+//! Fields without a `with` attribute are decoded with the `Decode`
+//! implementation for their type, which can be derived or written by hand.
+extern crate proc_macro;
+
+mod attributes;
+
+use attributes::{ContainerAttributes, FieldAttributes, VariantAttributes};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Fields, Type};
+
+#[proc_macro_derive(JsonDecode, attributes(json_decode))]
+pub fn derive_json_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "JsonDecode can't be derived for generic types",
+        ));
+    }
+
+    let container = ContainerAttributes::from_attributes(&input.attrs)?;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            if container.tag.is_some() || container.content.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "tag & content can only be used on enums",
+                ));
+            }
+            fields_decoder(quote!(Self), &data.fields, false)?
+        }
+        Data::Enum(data) => enum_decoder(&container, data)?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "JsonDecode can't be derived for unions",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    Ok(quote! {
+        impl ::json_decode::Decode for #ident {
+            fn decoder() -> ::json_decode::BoxDecoder<'static, Self> {
+                #body
+            }
+        }
+    })
+}
+
+fn enum_decoder(container: &ContainerAttributes, data: &DataEnum) -> syn::Result<TokenStream2> {
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let attributes = VariantAttributes::from_attributes(&variant.attrs)?;
+            let tag = attributes
+                .rename
+                .unwrap_or_else(|| variant.ident.to_string());
+            let ident = &variant.ident;
+            let internally_tagged = container.tag.is_some() && container.content.is_none();
+            if let (true, Fields::Unnamed(fields)) = (internally_tagged, &variant.fields) {
+                if fields.unnamed.len() != 1 {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "tuple variants without exactly one field can't be internally tagged, \
+                         add a content field to the json_decode attribute",
+                    ));
+                }
+            }
+            let decoder = fields_decoder(quote!(Self::#ident), &variant.fields, internally_tagged)?;
+            Ok(quote!((#tag, #decoder)))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    match (&container.tag, &container.content) {
+        (Some(tag), None) => Ok(quote!(::json_decode::tagged(#tag, vec![#(#variants),*]))),
+        (Some(tag), Some(content)) => Ok(quote!(
+            ::json_decode::adjacently_tagged(#tag, #content, vec![#(#variants),*])
+        )),
+        (None, None) => Ok(quote!(::json_decode::externally_tagged(
+            vec![#(#variants),*]
+        ))),
+        (None, Some(_)) => Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "content can only be used along with tag",
+        )),
+    }
+}
+
+/// Builds a decoder for a set of fields using `decode_with`.  `constructor` is
+/// the path used to construct the value, e.g. `Self` or `Self::Variant`.
+///
+/// Newtypes (i.e. a single unnamed field) are decoded from the inner value
+/// rather than a single element array.  Other unnamed fields are decoded from
+/// an array of exactly the same length.
+///
+/// Units are decoded from `null`, unless they are variants of an internally
+/// tagged enum, in which case they are given the whole object & accept it.
+fn fields_decoder(
+    constructor: TokenStream2,
+    fields: &Fields,
+    internally_tagged: bool,
+) -> syn::Result<TokenStream2> {
+    if let Fields::Unnamed(unnamed) = fields {
+        if unnamed.unnamed.len() == 1 {
+            let field = &unnamed.unnamed[0];
+            let attributes = FieldAttributes::from_attributes(&field.attrs)?;
+            if attributes.rename.is_some() || attributes.default {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "rename & default can only be used on named fields",
+                ));
+            }
+            let decoder = field_decoder(&field.ty, &attributes);
+            return Ok(quote!(::json_decode::map(|value| #constructor(value), #decoder)));
+        }
+    }
+
+    match fields {
+        Fields::Named(_) if fields.is_empty() => {
+            return Ok(quote!(::json_decode::map(
+                |_: ::std::vec::Vec<(::std::string::String, ())>| #constructor {},
+                ::json_decode::key_value_pairs(::json_decode::succeed(())),
+            )));
+        }
+        Fields::Unnamed(_) if fields.is_empty() => {
+            return Ok(quote!(::json_decode::map(
+                |_: ::std::vec::Vec<()>| #constructor(),
+                ::json_decode::exact_length(0, ::json_decode::list(::json_decode::succeed(()))),
+            )));
+        }
+        Fields::Unit if internally_tagged => {
+            return Ok(quote!(::json_decode::map(|()| #constructor, ::json_decode::succeed(()))));
+        }
+        Fields::Unit => {
+            return Ok(quote!(::json_decode::map(|()| #constructor, ::json_decode::null(()))));
+        }
+        _ => {}
+    }
+
+    let vars = (0..fields.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect::<Vec<_>>();
+
+    let steps = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let attributes = FieldAttributes::from_attributes(&field.attrs)?;
+            let decoder = field_decoder(&field.ty, &attributes);
+            match &field.ident {
+                Some(ident) => {
+                    let name = attributes
+                        .rename
+                        .unwrap_or_else(|| unraw(&ident.to_string()));
+                    if attributes.default {
+                        Ok(quote!(.custom(::json_decode::field_or_default(#name, #decoder))))
+                    } else {
+                        Ok(quote!(.required(#name, #decoder)))
+                    }
+                }
+                None => {
+                    if attributes.rename.is_some() || attributes.default {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "rename & default can only be used on named fields",
+                        ));
+                    }
+                    Ok(quote!(.custom(::json_decode::index(#i, #decoder))))
+                }
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let value = match fields {
+        Fields::Named(_) => {
+            let idents = fields.iter().map(|f| &f.ident);
+            quote!(#constructor { #(#idents: #vars),* })
+        }
+        _ => quote!(#constructor(#(#vars),*)),
+    };
+    let func = vars
+        .iter()
+        .rev()
+        .fold(value, |body, var| quote!(move |#var| #body));
+
+    let decoder = quote! {
+        ::json_decode::decode_with(#func)
+            #(#steps)*
+            .build()
+    };
+    match fields {
+        Fields::Unnamed(_) => {
+            let length = fields.len();
+            Ok(quote!(::json_decode::exact_length(#length, #decoder)))
+        }
+        _ => Ok(decoder),
+    }
+}
+
+fn field_decoder(ty: &Type, attributes: &FieldAttributes) -> TokenStream2 {
+    match &attributes.with {
+        Some(path) => quote!(#path()),
+        None => quote!(<#ty as ::json_decode::Decode>::decoder()),
+    }
+}
+
+fn unraw(ident: &str) -> String {
+    ident.trim_start_matches("r#").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn internally_tagged_tuple_variants_are_rejected() {
+        let input: DeriveInput = syn::parse_quote! {
+            #[json_decode(tag = "type")]
+            enum Shape {
+                Point(i32, i32),
+            }
+        };
+
+        assert_eq!(
+            expand(&input).unwrap_err().to_string(),
+            "tuple variants without exactly one field can't be internally tagged, \
+             add a content field to the json_decode attribute"
+        );
+    }
+
+    #[test]
+    fn attributes_on_newtype_fields_are_rejected() {
+        let input: DeriveInput = syn::parse_quote! {
+            struct Meters(#[json_decode(default)] f32);
+        };
+
+        assert_eq!(
+            expand(&input).unwrap_err().to_string(),
+            "rename & default can only be used on named fields"
+        );
+    }
+}
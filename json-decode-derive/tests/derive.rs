@@ -0,0 +1,252 @@
+use json_decode::{
+    and_then, fail, string, succeed, BoxDecoder, Decode, DecodeError, PathSegment, Snippet,
+};
+use json_decode_derive::JsonDecode;
+use std::collections::HashMap;
+
+#[derive(JsonDecode, Debug, PartialEq)]
+struct User {
+    id: u64,
+    name: String,
+    #[json_decode(rename = "emailAddress")]
+    email: Option<String>,
+    #[json_decode(default)]
+    tags: Vec<String>,
+    scores: HashMap<String, f64>,
+    #[json_decode(with = "upper_case")]
+    country: String,
+    address: Address,
+}
+
+#[derive(JsonDecode, Debug, PartialEq)]
+struct Address {
+    r#type: String,
+}
+
+fn upper_case() -> BoxDecoder<'static, String> {
+    and_then(
+        |s: String| {
+            if s.chars().all(|c| c.is_ascii_uppercase()) {
+                succeed(s)
+            } else {
+                fail("Expected an upper case string")
+            }
+        },
+        string(),
+    )
+}
+
+#[derive(JsonDecode, Debug, PartialEq)]
+struct Point(i32, i32);
+
+#[derive(JsonDecode, Debug, PartialEq)]
+struct Meters(f32);
+
+#[derive(JsonDecode, Debug, PartialEq)]
+struct Unit;
+
+#[derive(JsonDecode, Debug, PartialEq)]
+struct Empty {}
+
+#[derive(JsonDecode, Debug, PartialEq)]
+#[json_decode(tag = "type")]
+enum Shape {
+    #[json_decode(rename = "circle")]
+    Circle { radius: f64 },
+    #[json_decode(rename = "square")]
+    Square { side: f64 },
+    #[json_decode(rename = "empty")]
+    Empty,
+}
+
+#[derive(JsonDecode, Debug, PartialEq)]
+#[json_decode(tag = "tag", content = "content")]
+enum Message {
+    Text(String),
+    Move(Point),
+    Quit,
+}
+
+#[derive(JsonDecode, Debug, PartialEq)]
+enum Event {
+    Click { x: i32, y: i32 },
+    Key(String),
+    Focus,
+}
+
+#[test]
+fn deriving_a_struct_decoder() {
+    let json = serde_json::json!({
+        "id": 1,
+        "name": "Graeme",
+        "emailAddress": null,
+        "scores": {"one": 1.0},
+        "country": "GB",
+        "address": {"type": "home"}
+    });
+
+    assert_eq!(
+        User::decoder().decode(&json),
+        Ok(User {
+            id: 1,
+            name: "Graeme".into(),
+            email: None,
+            tags: vec![],
+            scores: vec![("one".to_string(), 1.0)].into_iter().collect(),
+            country: "GB".into(),
+            address: Address {
+                r#type: "home".into()
+            }
+        })
+    );
+}
+
+#[test]
+fn derived_struct_decoders_report_paths() {
+    let json = serde_json::json!({
+        "id": 1,
+        "name": "Graeme",
+        "emailAddress": null,
+        "scores": {},
+        "country": "gb",
+        "address": {}
+    });
+
+    let errors = User::decoder().decode_all_errors(&json).unwrap_err();
+    assert_eq!(
+        errors.iter().map(|e| e.path()).collect::<Vec<_>>(),
+        vec![
+            vec![PathSegment::Field("country".into())],
+            vec![PathSegment::Field("address".into())]
+        ]
+    );
+}
+
+#[test]
+fn deriving_tuple_struct_decoders() {
+    assert_eq!(
+        Point::decoder().decode(&serde_json::json!([1, 2])),
+        Ok(Point(1, 2))
+    );
+    assert_eq!(
+        Point::decoder().decode(&serde_json::json!([1, 2, 3])),
+        Err(DecodeError::IncorrectLength(2, 3))
+    );
+    assert_eq!(
+        Meters::decoder().decode(&serde_json::json!(1.5)),
+        Ok(Meters(1.5))
+    );
+}
+
+#[test]
+fn deriving_unit_and_empty_struct_decoders() {
+    assert_eq!(Unit::decoder().decode(&serde_json::json!(null)), Ok(Unit));
+    assert_eq!(
+        Unit::decoder()
+            .decode(&serde_json::json!(42))
+            .unwrap_err()
+            .code(),
+        "incorrect_type"
+    );
+    assert_eq!(
+        Empty::decoder().decode(&serde_json::json!({"extra": 1})),
+        Ok(Empty {})
+    );
+    assert_eq!(
+        Empty::decoder()
+            .decode(&serde_json::json!("not an object"))
+            .unwrap_err()
+            .code(),
+        "incorrect_type"
+    );
+}
+
+#[test]
+fn deriving_internally_tagged_enum_decoders() {
+    assert_eq!(
+        Shape::decoder().decode(&serde_json::json!({"type": "circle", "radius": 1.0})),
+        Ok(Shape::Circle { radius: 1.0 })
+    );
+    assert_eq!(
+        Shape::decoder().decode(&serde_json::json!({"type": "empty"})),
+        Ok(Shape::Empty)
+    );
+    assert_eq!(
+        Shape::decoder()
+            .decode(&serde_json::json!({"type": "triangle"}))
+            .unwrap_err()
+            .root_cause(),
+        &DecodeError::UnknownTag(
+            Snippet::new(&serde_json::json!("triangle")),
+            vec!["circle".into(), "square".into(), "empty".into()]
+        )
+    );
+}
+
+#[test]
+fn deriving_adjacently_tagged_enum_decoders() {
+    assert_eq!(
+        Message::decoder().decode(&serde_json::json!({"tag": "Text", "content": "hello"})),
+        Ok(Message::Text("hello".into()))
+    );
+    assert_eq!(
+        Message::decoder().decode(&serde_json::json!({"tag": "Move", "content": [1, 2]})),
+        Ok(Message::Move(Point(1, 2)))
+    );
+    assert_eq!(
+        Message::decoder().decode(&serde_json::json!({"tag": "Quit"})),
+        Ok(Message::Quit)
+    );
+    assert_eq!(
+        Message::decoder().decode(&serde_json::json!({"tag": "Quit", "content": null})),
+        Ok(Message::Quit)
+    );
+    assert_eq!(
+        Message::decoder()
+            .decode(&serde_json::json!({"tag": "Quit", "content": {"x": 1}}))
+            .unwrap_err()
+            .code(),
+        "incorrect_type"
+    );
+    assert_eq!(
+        Message::decoder()
+            .decode(&serde_json::json!({"tag": "Text"}))
+            .unwrap_err()
+            .code(),
+        "missing_field"
+    );
+}
+
+#[test]
+fn deriving_externally_tagged_enum_decoders() {
+    assert_eq!(
+        Event::decoder().decode(&serde_json::json!({"Click": {"x": 1, "y": 2}})),
+        Ok(Event::Click { x: 1, y: 2 })
+    );
+    assert_eq!(
+        Event::decoder().decode(&serde_json::json!({"Key": "a"})),
+        Ok(Event::Key("a".into()))
+    );
+    assert_eq!(
+        Event::decoder().decode(&serde_json::json!("Focus")),
+        Ok(Event::Focus)
+    );
+    assert_eq!(
+        Event::decoder().decode(&serde_json::json!({"Focus": null})),
+        Ok(Event::Focus)
+    );
+    assert_eq!(
+        Event::decoder()
+            .decode(&serde_json::json!({"Focus": [1, 2, 3]}))
+            .unwrap_err()
+            .code(),
+        "incorrect_type"
+    );
+    assert_eq!(
+        Event::decoder()
+            .decode(&serde_json::json!("Key"))
+            .unwrap_err()
+            .code(),
+        "incorrect_type"
+    );
+}
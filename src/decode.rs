@@ -0,0 +1,195 @@
+use crate::{
+    decoders::{
+        boolean, dict, exact_length, float, index, integer, json, list, map, option, string,
+        unsigned_integer,
+    },
+    map10, map11, map12, map2, map3, map4, map5, map6, map7, map8, map9, BoxDecoder, DecodeError,
+    Decoder, JsonType, Snippet,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A type that has a canonical decoder.
+///
+/// This is implemented for primitives, `String`, `serde_json::Value` and
+/// common containers of other `Decode` types, so generic code can ask for "the
+/// normal decoder" for a type via `decoder::<T>()`.  It can be derived with
+/// `#[derive(JsonDecode)]` when the `derive` feature is enabled.
+pub trait Decode: Sized {
+    fn decoder() -> BoxDecoder<'static, Self>;
+}
+
+/// Returns the canonical decoder for `T`.  See `Decode`.
+pub fn decoder<T: Decode>() -> BoxDecoder<'static, T> {
+    T::decoder()
+}
+
+macro_rules! impl_decode {
+    ($($t:ty => $decoder:expr),+ $(,)?) => {
+        $(
+            impl Decode for $t {
+                fn decoder() -> BoxDecoder<'static, Self> {
+                    $decoder
+                }
+            }
+        )+
+    };
+}
+
+impl_decode!(
+    String => string(),
+    bool => boolean(),
+    i8 => integer(),
+    i16 => integer(),
+    i32 => integer(),
+    i64 => integer(),
+    i128 => Box::new(I128Decoder),
+    isize => integer(),
+    u8 => unsigned_integer(),
+    u16 => unsigned_integer(),
+    u32 => unsigned_integer(),
+    u64 => unsigned_integer(),
+    u128 => unsigned_integer(),
+    usize => unsigned_integer(),
+    f64 => float(),
+    f32 => Box::new(F32Decoder),
+    serde_json::Value => json(),
+);
+
+/// serde_json holds integers as either an `i64` or a `u64`, so an `i128` accepts
+/// anything that fits in either.  Larger integers are parsed as floats, and
+/// can't be decoded exactly.
+struct I128Decoder;
+
+impl<'a> Decoder<'a, i128> for I128Decoder {
+    fn decode(&self, value: &serde_json::Value) -> Result<i128, DecodeError> {
+        match value {
+            serde_json::Value::Number(n) => n
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| n.as_u64().map(i128::from))
+                .ok_or_else(|| DecodeError::InvalidInteger(Snippet::new(value))),
+            _ => Err(DecodeError::IncorrectType(
+                JsonType::Number,
+                Snippet::new(value),
+            )),
+        }
+    }
+}
+
+/// Numbers too large for an `f32` are rejected rather than becoming infinite.
+struct F32Decoder;
+
+impl<'a> Decoder<'a, f32> for F32Decoder {
+    fn decode(&self, value: &serde_json::Value) -> Result<f32, DecodeError> {
+        let float: f64 = crate::typed::float().decode(value)?;
+        let narrowed = float as f32;
+        if narrowed.is_infinite() {
+            return Err(DecodeError::NumberOutOfRange(Snippet::new(value)));
+        }
+        Ok(narrowed)
+    }
+}
+
+impl<T> Decode for Option<T>
+where
+    T: Decode + Send + Sync + 'static,
+{
+    fn decoder() -> BoxDecoder<'static, Self> {
+        option(T::decoder())
+    }
+}
+
+impl<T> Decode for Box<T>
+where
+    T: Decode + 'static,
+{
+    fn decoder() -> BoxDecoder<'static, Self> {
+        map(Box::new, T::decoder())
+    }
+}
+
+impl<T> Decode for Vec<T>
+where
+    T: Decode + Send + Sync + 'static,
+{
+    fn decoder() -> BoxDecoder<'static, Self> {
+        list(T::decoder())
+    }
+}
+
+impl<T> Decode for VecDeque<T>
+where
+    T: Decode + Send + Sync + 'static,
+{
+    fn decoder() -> BoxDecoder<'static, Self> {
+        list(T::decoder())
+    }
+}
+
+impl<T> Decode for HashSet<T>
+where
+    T: Decode + Eq + Hash + Send + Sync + 'static,
+{
+    fn decoder() -> BoxDecoder<'static, Self> {
+        list(T::decoder())
+    }
+}
+
+impl<T> Decode for BTreeSet<T>
+where
+    T: Decode + Ord + Send + Sync + 'static,
+{
+    fn decoder() -> BoxDecoder<'static, Self> {
+        list(T::decoder())
+    }
+}
+
+impl<T> Decode for HashMap<String, T>
+where
+    T: Decode + Send + Sync + 'static,
+{
+    fn decoder() -> BoxDecoder<'static, Self> {
+        dict(T::decoder())
+    }
+}
+
+impl<T> Decode for BTreeMap<String, T>
+where
+    T: Decode + Send + Sync + 'static,
+{
+    fn decoder() -> BoxDecoder<'static, Self> {
+        dict(T::decoder())
+    }
+}
+
+/// Tuples are decoded from arrays, with one element per item in the tuple.
+macro_rules! impl_decode_for_tuple {
+    ($map_fn:ident, $length:expr, $($t:ident $i:tt),+) => {
+        impl<$($t),+> Decode for ($($t,)+)
+        where
+            $($t: Decode + 'static,)+
+        {
+            #[allow(non_snake_case)]
+            fn decoder() -> BoxDecoder<'static, Self> {
+                exact_length(
+                    $length,
+                    $map_fn(|$($t),+| ($($t,)+), $(index($i, $t::decoder())),+),
+                )
+            }
+        }
+    };
+}
+
+impl_decode_for_tuple!(map, 1, A 0);
+impl_decode_for_tuple!(map2, 2, A 0, B 1);
+impl_decode_for_tuple!(map3, 3, A 0, B 1, C 2);
+impl_decode_for_tuple!(map4, 4, A 0, B 1, C 2, D 3);
+impl_decode_for_tuple!(map5, 5, A 0, B 1, C 2, D 3, E 4);
+impl_decode_for_tuple!(map6, 6, A 0, B 1, C 2, D 3, E 4, F 5);
+impl_decode_for_tuple!(map7, 7, A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_decode_for_tuple!(map8, 8, A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_decode_for_tuple!(map9, 9, A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_decode_for_tuple!(map10, 10, A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_decode_for_tuple!(map11, 11, A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_decode_for_tuple!(map12, 12, A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
@@ -1,11 +1,23 @@
-use super::{DecodeError, Decoder};
+use super::{DecodeError, DecodeErrors, Decoder, JsonType, Snippet};
 use std::convert::{TryFrom, TryInto};
 use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 pub type BoxDecoder<'a, T> = Box<dyn Decoder<'a, T> + 'a + Send + Sync>;
 
-pub fn field<'a, T>(field_name: &str, decoder: BoxDecoder<'a, T>) -> BoxDecoder<'a, T>
+/// A decoder that can be cheaply cloned & shared between threads, so that it
+/// only needs to be built once.  Create one with `Decoder::shared`.
+///
+/// This can be passed to any of the decoder functions that accept
+/// `impl Decoder`, or boxed with `Decoder::boxed` for those that need a
+/// `BoxDecoder`.
+pub type ArcDecoder<'a, T> = Arc<dyn Decoder<'a, T> + 'a + Send + Sync>;
+
+pub fn field<'a, T>(
+    field_name: &str,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, T>
 where
     T: 'a,
 {
@@ -15,26 +27,515 @@ where
     })
 }
 
-pub struct FieldDecoder<'a, DecodesTo> {
-    field_name: String,
-    inner_decoder: BoxDecoder<'a, DecodesTo>,
+pub struct FieldDecoder<D> {
+    pub(crate) field_name: String,
+    pub(crate) inner_decoder: D,
 }
 
-impl<'a, DecodesTo> Decoder<'a, DecodesTo> for FieldDecoder<'a, DecodesTo> {
+impl<'a, DecodesTo, D> Decoder<'a, DecodesTo> for FieldDecoder<D>
+where
+    D: Decoder<'a, DecodesTo>,
+{
     fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
         match value {
             serde_json::Value::Object(map) => map
                 .get(&self.field_name)
                 .ok_or_else(|| {
-                    DecodeError::MissingField(self.field_name.clone(), value.to_string())
+                    DecodeError::MissingField(self.field_name.clone(), Snippet::new(value))
                 })
-                .and_then(|inner_value| (*self.inner_decoder).decode(inner_value)),
+                .and_then(|inner_value| {
+                    self.inner_decoder
+                        .decode(inner_value)
+                        .map_err(|e| e.in_field(&self.field_name))
+                }),
             _ => Err(DecodeError::IncorrectType(
-                "Object".to_string(),
-                value.to_string(),
+                JsonType::Object,
+                Snippet::new(value),
             )),
         }
     }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        match value {
+            serde_json::Value::Object(map) => match map.get(&self.field_name) {
+                Some(inner_value) => self
+                    .inner_decoder
+                    .decode_all_errors(inner_value)
+                    .map_err(|errors| errors.map(|e| e.in_field(&self.field_name))),
+                None => Err(DecodeError::MissingField(
+                    self.field_name.clone(),
+                    Snippet::new(value),
+                )
+                .into()),
+            },
+            _ => Err(DecodeError::IncorrectType(JsonType::Object, Snippet::new(value)).into()),
+        }
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Object(mut map) => match map.remove(&self.field_name) {
+                Some(inner_value) => self
+                    .inner_decoder
+                    .decode_owned(inner_value)
+                    .map_err(|e| e.in_field(&self.field_name)),
+                None => Err(DecodeError::MissingField(
+                    self.field_name.clone(),
+                    Snippet::new(&serde_json::Value::Object(map)),
+                )),
+            },
+            _ => self.decode(&value),
+        }
+    }
+}
+
+/// Decodes a value nested inside several objects, e.g.
+/// `at(&["data", "user", "name"], string())`.
+pub fn at<'a, T>(
+    path: &[&str],
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, T>
+where
+    T: 'a,
+{
+    Box::new(AtDecoder {
+        path: path.iter().map(ToString::to_string).collect(),
+        inner_decoder: decoder,
+    })
+}
+
+pub struct AtDecoder<D> {
+    pub(crate) path: Vec<String>,
+    pub(crate) inner_decoder: D,
+}
+
+impl<D> AtDecoder<D> {
+    /// Follows the path, returning the value at the end of it.
+    fn follow<'v>(
+        &self,
+        value: &'v serde_json::Value,
+    ) -> Result<&'v serde_json::Value, DecodeError> {
+        let mut current = value;
+        for (depth, field_name) in self.path.iter().enumerate() {
+            current = match current {
+                serde_json::Value::Object(map) => map.get(field_name).ok_or_else(|| {
+                    DecodeError::MissingField(field_name.clone(), Snippet::new(current))
+                }),
+                _ => Err(DecodeError::IncorrectType(
+                    JsonType::Object,
+                    Snippet::new(current),
+                )),
+            }
+            .map_err(|e| self.in_path(e, depth))?;
+        }
+        Ok(current)
+    }
+
+    /// Wraps `error` in the first `depth` fields of the path, as if it had come
+    /// from nested `field` decoders.
+    fn in_path(&self, error: DecodeError, depth: usize) -> DecodeError {
+        self.path[..depth]
+            .iter()
+            .rev()
+            .fold(error, |error, field_name| error.in_field(field_name))
+    }
+}
+
+impl<'a, DecodesTo, D> Decoder<'a, DecodesTo> for AtDecoder<D>
+where
+    D: Decoder<'a, DecodesTo>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        self.inner_decoder
+            .decode(self.follow(value)?)
+            .map_err(|e| self.in_path(e, self.path.len()))
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        self.inner_decoder
+            .decode_all_errors(self.follow(value)?)
+            .map_err(|errors| errors.map(|e| self.in_path(e, self.path.len())))
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        let mut current = value;
+        for (depth, field_name) in self.path.iter().enumerate() {
+            current = match current {
+                serde_json::Value::Object(mut map) => match map.remove(field_name) {
+                    Some(inner_value) => inner_value,
+                    None => {
+                        let error = DecodeError::MissingField(
+                            field_name.clone(),
+                            Snippet::new(&serde_json::Value::Object(map)),
+                        );
+                        return Err(self.in_path(error, depth));
+                    }
+                },
+                other => {
+                    let error = DecodeError::IncorrectType(JsonType::Object, Snippet::new(&other));
+                    return Err(self.in_path(error, depth));
+                }
+            };
+        }
+        self.inner_decoder
+            .decode_owned(current)
+            .map_err(|e| self.in_path(e, self.path.len()))
+    }
+}
+
+/// Checks that an array has exactly `length` elements before decoding it with
+/// `decoder`, failing with `DecodeError::IncorrectLength` otherwise.
+///
+/// This is useful along with `index` & the `mapN` functions when decoding
+/// arrays that are really tuples, so that extra elements aren't silently
+/// ignored.  Values that aren't arrays are passed to `decoder` as is.
+pub fn exact_length<'a, T>(
+    length: usize,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, T>
+where
+    T: 'a,
+{
+    Box::new(ExactLengthDecoder { length, decoder })
+}
+
+pub struct ExactLengthDecoder<D> {
+    pub(crate) length: usize,
+    pub(crate) decoder: D,
+}
+
+impl<D> ExactLengthDecoder<D> {
+    fn check_length(&self, value: &serde_json::Value) -> Result<(), DecodeError> {
+        match value {
+            serde_json::Value::Array(vec) if vec.len() != self.length => {
+                Err(DecodeError::IncorrectLength(self.length, vec.len()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a, DecodesTo, D> Decoder<'a, DecodesTo> for ExactLengthDecoder<D>
+where
+    D: Decoder<'a, DecodesTo>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        self.check_length(value)?;
+        self.decoder.decode(value)
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        self.check_length(value)?;
+        self.decoder.decode_all_errors(value)
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        self.check_length(&value)?;
+        self.decoder.decode_owned(value)
+    }
+}
+
+/// Decodes the element at position `index` of an array.
+pub fn index<'a, T>(
+    index: usize,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, T>
+where
+    T: 'a,
+{
+    Box::new(IndexDecoder {
+        index,
+        inner_decoder: decoder,
+    })
+}
+
+pub struct IndexDecoder<D> {
+    pub(crate) index: usize,
+    pub(crate) inner_decoder: D,
+}
+
+impl<D> IndexDecoder<D> {
+    fn element<'v>(
+        &self,
+        value: &'v serde_json::Value,
+    ) -> Result<&'v serde_json::Value, DecodeError> {
+        match value {
+            serde_json::Value::Array(vec) => vec
+                .get(self.index)
+                .ok_or(DecodeError::IndexOutOfBounds(self.index, vec.len())),
+            _ => Err(DecodeError::IncorrectType(
+                JsonType::Array,
+                Snippet::new(value),
+            )),
+        }
+    }
+}
+
+impl<'a, DecodesTo, D> Decoder<'a, DecodesTo> for IndexDecoder<D>
+where
+    D: Decoder<'a, DecodesTo>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        self.inner_decoder
+            .decode(self.element(value)?)
+            .map_err(|e| e.at_index(self.index))
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        self.inner_decoder
+            .decode_all_errors(self.element(value)?)
+            .map_err(|errors| errors.map(|e| e.at_index(self.index)))
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Array(mut vec) if self.index < vec.len() => self
+                .inner_decoder
+                .decode_owned(vec.swap_remove(self.index))
+                .map_err(|e| e.at_index(self.index)),
+            _ => self.decode(&value),
+        }
+    }
+}
+
+/// Decodes an optional field of an object.
+///
+/// Returns `None` if the field is missing, otherwise decodes the field with
+/// `decoder`.  Unlike `option`, `null` is passed on to `decoder` - combine the
+/// two (e.g. `optional_field("name", option(string()))`) if the field might be
+/// `null` as well as missing.
+pub fn optional_field<'a, T>(
+    field_name: &str,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, Option<T>>
+where
+    T: 'a,
+{
+    Box::new(OptionalFieldDecoder {
+        field_name: field_name.to_string(),
+        inner_decoder: decoder,
+    })
+}
+
+pub struct OptionalFieldDecoder<D> {
+    pub(crate) field_name: String,
+    pub(crate) inner_decoder: D,
+}
+
+impl<'a, DecodesTo, D> Decoder<'a, Option<DecodesTo>> for OptionalFieldDecoder<D>
+where
+    D: Decoder<'a, DecodesTo>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<Option<DecodesTo>, DecodeError> {
+        match value {
+            serde_json::Value::Object(map) => map
+                .get(&self.field_name)
+                .map(|inner_value| {
+                    self.inner_decoder
+                        .decode(inner_value)
+                        .map_err(|e| e.in_field(&self.field_name))
+                })
+                .transpose(),
+            _ => Err(DecodeError::IncorrectType(
+                JsonType::Object,
+                Snippet::new(value),
+            )),
+        }
+    }
+
+    fn decode_all_errors(
+        &self,
+        value: &serde_json::Value,
+    ) -> Result<Option<DecodesTo>, DecodeErrors> {
+        match value {
+            serde_json::Value::Object(map) => map
+                .get(&self.field_name)
+                .map(|inner_value| {
+                    self.inner_decoder
+                        .decode_all_errors(inner_value)
+                        .map_err(|errors| errors.map(|e| e.in_field(&self.field_name)))
+                })
+                .transpose(),
+            _ => Err(DecodeError::IncorrectType(JsonType::Object, Snippet::new(value)).into()),
+        }
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<Option<DecodesTo>, DecodeError> {
+        match value {
+            serde_json::Value::Object(mut map) => map
+                .remove(&self.field_name)
+                .map(|inner_value| {
+                    self.inner_decoder
+                        .decode_owned(inner_value)
+                        .map_err(|e| e.in_field(&self.field_name))
+                })
+                .transpose(),
+            _ => self.decode(&value),
+        }
+    }
+}
+
+/// Decodes a field of an object, falling back to `default` if the field is
+/// missing or `null`.
+pub fn field_or<'a, T>(
+    field_name: &str,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+    default: T,
+) -> BoxDecoder<'a, T>
+where
+    T: Clone + Send + Sync + 'a,
+{
+    Box::new(FieldOrDecoder {
+        field_name: field_name.to_string(),
+        inner_decoder: decoder,
+        default: move || default.clone(),
+    })
+}
+
+/// Decodes a field of an object, falling back to `T::default()` if the field is
+/// missing or `null`.
+pub fn field_or_default<'a, T>(
+    field_name: &str,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, T>
+where
+    T: Default + 'a,
+{
+    Box::new(FieldOrDecoder {
+        field_name: field_name.to_string(),
+        inner_decoder: decoder,
+        default: T::default,
+    })
+}
+
+pub struct FieldOrDecoder<D, F> {
+    pub(crate) field_name: String,
+    pub(crate) inner_decoder: D,
+    pub(crate) default: F,
+}
+
+impl<'a, DecodesTo, D, F> Decoder<'a, DecodesTo> for FieldOrDecoder<D, F>
+where
+    D: Decoder<'a, DecodesTo>,
+    F: Fn() -> DecodesTo,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Object(map) => match map.get(&self.field_name) {
+                None | Some(serde_json::Value::Null) => Ok((self.default)()),
+                Some(inner_value) => self
+                    .inner_decoder
+                    .decode(inner_value)
+                    .map_err(|e| e.in_field(&self.field_name)),
+            },
+            _ => Err(DecodeError::IncorrectType(
+                JsonType::Object,
+                Snippet::new(value),
+            )),
+        }
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        match value {
+            serde_json::Value::Object(map) => match map.get(&self.field_name) {
+                None | Some(serde_json::Value::Null) => Ok((self.default)()),
+                Some(inner_value) => self
+                    .inner_decoder
+                    .decode_all_errors(inner_value)
+                    .map_err(|errors| errors.map(|e| e.in_field(&self.field_name))),
+            },
+            _ => Err(DecodeError::IncorrectType(JsonType::Object, Snippet::new(value)).into()),
+        }
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Object(mut map) => match map.remove(&self.field_name) {
+                None | Some(serde_json::Value::Null) => Ok((self.default)()),
+                Some(inner_value) => self
+                    .inner_decoder
+                    .decode_owned(inner_value)
+                    .map_err(|e| e.in_field(&self.field_name)),
+            },
+            _ => self.decode(&value),
+        }
+    }
+}
+
+/// Decodes `null` as `default`, and anything else with `decoder`.
+pub fn with_default<'a, T>(
+    default: T,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, T>
+where
+    T: Clone + Send + Sync + 'a,
+{
+    Box::new(WithDefaultDecoder {
+        inner_decoder: decoder,
+        default: move || default.clone(),
+    })
+}
+
+pub struct WithDefaultDecoder<D, F> {
+    pub(crate) inner_decoder: D,
+    pub(crate) default: F,
+}
+
+impl<'a, DecodesTo, D, F> Decoder<'a, DecodesTo> for WithDefaultDecoder<D, F>
+where
+    D: Decoder<'a, DecodesTo>,
+    F: Fn() -> DecodesTo,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Null => Ok((self.default)()),
+            _ => self.inner_decoder.decode(value),
+        }
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        match value {
+            serde_json::Value::Null => Ok((self.default)()),
+            _ => self.inner_decoder.decode_all_errors(value),
+        }
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Null => Ok((self.default)()),
+            _ => self.inner_decoder.decode_owned(value),
+        }
+    }
+}
+
+/// The state of a field that might be missing, `null` or present.
+///
+/// This is mostly useful for PATCH style APIs where a missing field means
+/// "leave this alone" and a `null` field means "clear this".
+#[derive(Debug, Clone, PartialEq)]
+pub enum Patch<T> {
+    Absent,
+    Null,
+    Value(T),
+}
+
+/// Decodes a field of an object into a `Patch`, distinguishing between the
+/// field being missing, being `null` and having a value.
+pub fn patch_field<'a, T>(
+    field_name: &str,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, Patch<T>>
+where
+    T: 'a + Send + Sync,
+{
+    map(
+        |value| match value {
+            None => Patch::Absent,
+            Some(None) => Patch::Null,
+            Some(Some(value)) => Patch::Value(value),
+        },
+        optional_field(field_name, option(decoder)),
+    )
 }
 
 pub fn string() -> BoxDecoder<'static, String> {
@@ -48,16 +549,23 @@ impl<'a> Decoder<'a, String> for StringDecoder {
         match value {
             serde_json::Value::String(s) => Ok(s.clone()),
             _ => Err(DecodeError::IncorrectType(
-                "String".to_string(),
-                value.to_string(),
+                JsonType::String,
+                Snippet::new(value),
             )),
         }
     }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<String, DecodeError> {
+        match value {
+            serde_json::Value::String(s) => Ok(s),
+            _ => self.decode(&value),
+        }
+    }
 }
 
-pub fn integer<I: TryFrom<i64>>() -> BoxDecoder<'static, I>
+pub fn integer<I>() -> BoxDecoder<'static, I>
 where
-    I: 'static + Send + Sync,
+    I: TryFrom<i64> + 'static + Send + Sync,
 {
     Box::new(IntDecoder {
         phantom: PhantomData,
@@ -65,7 +573,7 @@ where
 }
 
 pub struct IntDecoder<I: TryFrom<i64>> {
-    phantom: PhantomData<I>,
+    pub(crate) phantom: PhantomData<I>,
 }
 
 impl<'a, I> Decoder<'a, I> for IntDecoder<I>
@@ -77,23 +585,23 @@ where
             serde_json::Value::Number(n) => {
                 let int64 = n
                     .as_i64()
-                    .ok_or_else(|| DecodeError::InvalidInteger(value.to_string()))?;
+                    .ok_or_else(|| DecodeError::InvalidInteger(Snippet::new(value)))?;
 
-                Ok(int64.try_into().map_err(|_| {
-                    DecodeError::IntegerOverflow(value.to_string(), std::any::type_name::<I>())
-                })?)
+                int64
+                    .try_into()
+                    .map_err(|_| DecodeError::NumberOutOfRange(Snippet::new(value)))
             }
             _ => Err(DecodeError::IncorrectType(
-                "Number".to_string(),
-                value.to_string(),
+                JsonType::Number,
+                Snippet::new(value),
             )),
         }
     }
 }
 
-pub fn unsigned_integer<'a, I: TryFrom<u64>>() -> Box<dyn Decoder<'a, I> + 'a>
+pub fn unsigned_integer<'a, I>() -> BoxDecoder<'a, I>
 where
-    I: 'a,
+    I: TryFrom<u64> + 'a + Send + Sync,
 {
     Box::new(UIntDecoder {
         phantom: PhantomData,
@@ -101,7 +609,7 @@ where
 }
 
 pub struct UIntDecoder<I: TryFrom<u64>> {
-    phantom: PhantomData<I>,
+    pub(crate) phantom: PhantomData<I>,
 }
 
 impl<'a, I> Decoder<'a, I> for UIntDecoder<I>
@@ -113,23 +621,23 @@ where
             serde_json::Value::Number(n) => {
                 let uint64 = n
                     .as_u64()
-                    .ok_or_else(|| DecodeError::InvalidInteger(value.to_string()))?;
+                    .ok_or_else(|| DecodeError::InvalidInteger(Snippet::new(value)))?;
 
-                Ok(uint64.try_into().map_err(|_| {
-                    DecodeError::IntegerOverflow(value.to_string(), std::any::type_name::<I>())
-                })?)
+                uint64
+                    .try_into()
+                    .map_err(|_| DecodeError::NumberOutOfRange(Snippet::new(value)))
             }
             _ => Err(DecodeError::IncorrectType(
-                "Number".to_string(),
-                value.to_string(),
+                JsonType::Number,
+                Snippet::new(value),
             )),
         }
     }
 }
 
-pub fn float<F: From<f64>>() -> BoxDecoder<'static, F>
+pub fn float<F>() -> BoxDecoder<'static, F>
 where
-    F: 'static + Send + Sync,
+    F: From<f64> + 'static + Send + Sync,
 {
     Box::new(FloatDecoder {
         phantom: PhantomData,
@@ -137,7 +645,7 @@ where
 }
 
 pub struct FloatDecoder<I: From<f64>> {
-    phantom: PhantomData<I>,
+    pub(crate) phantom: PhantomData<I>,
 }
 
 // TODO: Probably don't need from - just force f64 etc.
@@ -150,10 +658,10 @@ where
             serde_json::Value::Number(n) => n
                 .as_f64()
                 .map(Into::into)
-                .ok_or_else(|| DecodeError::InvalidInteger(value.to_string())),
+                .ok_or_else(|| DecodeError::InvalidInteger(Snippet::new(value))),
             _ => Err(DecodeError::IncorrectType(
-                "Number".to_string(),
-                value.to_string(),
+                JsonType::Number,
+                Snippet::new(value),
             )),
         }
     }
@@ -170,15 +678,42 @@ impl<'a> Decoder<'a, bool> for BooleanDecoder {
         match value {
             serde_json::Value::Bool(b) => Ok(*b),
             _ => Err(DecodeError::IncorrectType(
-                "Boolean".to_string(),
-                value.to_string(),
+                JsonType::Boolean,
+                Snippet::new(value),
+            )),
+        }
+    }
+}
+
+/// Decodes `null` to `value`, failing on anything else.
+pub fn null<'a, V>(value: V) -> BoxDecoder<'a, V>
+where
+    V: Clone + Send + Sync + 'a,
+{
+    Box::new(NullDecoder { value })
+}
+
+pub struct NullDecoder<V> {
+    pub(crate) value: V,
+}
+
+impl<'a, V> Decoder<'a, V> for NullDecoder<V>
+where
+    V: Clone + Send + Sync + 'a,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<V, DecodeError> {
+        match value {
+            serde_json::Value::Null => Ok(self.value.clone()),
+            _ => Err(DecodeError::IncorrectType(
+                JsonType::Null,
+                Snippet::new(value),
             )),
         }
     }
 }
 
 pub fn option<'a, DecodesTo>(
-    decoder: BoxDecoder<'a, DecodesTo>,
+    decoder: impl Decoder<'a, DecodesTo> + 'a + Send + Sync,
 ) -> BoxDecoder<'a, Option<DecodesTo>>
 where
     DecodesTo: 'a + Send + Sync,
@@ -188,13 +723,13 @@ where
     })
 }
 
-pub struct OptionDecoder<'a, DecodesTo> {
-    inner_decoder: BoxDecoder<'a, DecodesTo>,
+pub struct OptionDecoder<D> {
+    pub(crate) inner_decoder: D,
 }
 
-impl<'a, DecodesTo> Decoder<'a, Option<DecodesTo>> for OptionDecoder<'a, DecodesTo>
+impl<'a, DecodesTo, D> Decoder<'a, Option<DecodesTo>> for OptionDecoder<D>
 where
-    DecodesTo: 'a,
+    D: Decoder<'a, DecodesTo>,
 {
     fn decode(&self, value: &serde_json::Value) -> Result<Option<DecodesTo>, DecodeError> {
         match value {
@@ -202,11 +737,30 @@ where
             _ => self.inner_decoder.decode(value).map(Some),
         }
     }
+
+    fn decode_all_errors(
+        &self,
+        value: &serde_json::Value,
+    ) -> Result<Option<DecodesTo>, DecodeErrors> {
+        match value {
+            serde_json::Value::Null => Ok(None),
+            _ => self.inner_decoder.decode_all_errors(value).map(Some),
+        }
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<Option<DecodesTo>, DecodeError> {
+        match value {
+            serde_json::Value::Null => Ok(None),
+            _ => self.inner_decoder.decode_owned(value).map(Some),
+        }
+    }
 }
 
 // TODO: Difficulties using this due to type inference problems
 // look to re-work the interface somehow
-pub fn list<'a, Item, Collection>(decoder: BoxDecoder<'a, Item>) -> BoxDecoder<'a, Collection>
+pub fn list<'a, Item, Collection>(
+    decoder: impl Decoder<'a, Item> + 'a + Send + Sync,
+) -> BoxDecoder<'a, Collection>
 where
     Collection: FromIterator<Item> + 'a + Send + Sync,
     Item: 'a,
@@ -217,51 +771,684 @@ where
     })
 }
 
-pub struct ListDecoder<'a, Item, DecodesTo: FromIterator<Item>> {
-    phantom: PhantomData<DecodesTo>,
-    inner_decoder: BoxDecoder<'a, Item>,
+pub struct ListDecoder<D, Item, DecodesTo> {
+    pub(crate) phantom: PhantomData<fn() -> (Item, DecodesTo)>,
+    pub(crate) inner_decoder: D,
 }
 
-impl<'a, Item, DecodesTo> Decoder<'a, DecodesTo> for ListDecoder<'a, Item, DecodesTo>
+impl<'a, Item, DecodesTo, D> Decoder<'a, DecodesTo> for ListDecoder<D, Item, DecodesTo>
 where
+    D: Decoder<'a, Item>,
     DecodesTo: FromIterator<Item>,
 {
     fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
         match value {
             serde_json::Value::Array(vec) => vec
                 .iter()
-                .map(|item| (*self.inner_decoder).decode(item))
+                .enumerate()
+                .map(|(index, item)| {
+                    self.inner_decoder
+                        .decode(item)
+                        .map_err(|e| e.at_index(index))
+                })
                 .collect(),
             _ => Err(DecodeError::IncorrectType(
-                "Array".to_string(),
-                value.to_string(),
+                JsonType::Array,
+                Snippet::new(value),
             )),
         }
     }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        match value {
+            serde_json::Value::Array(vec) => {
+                let mut errors = Vec::new();
+                let collection = vec
+                    .iter()
+                    .enumerate()
+                    .filter_map(
+                        |(index, item)| match self.inner_decoder.decode_all_errors(item) {
+                            Ok(item) => Some(item),
+                            Err(item_errors) => {
+                                errors.extend(item_errors.into_iter().map(|e| e.at_index(index)));
+                                None
+                            }
+                        },
+                    )
+                    .collect();
+                if errors.is_empty() {
+                    Ok(collection)
+                } else {
+                    Err(DecodeErrors::new(errors))
+                }
+            }
+            _ => Err(DecodeError::IncorrectType(JsonType::Array, Snippet::new(value)).into()),
+        }
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Array(vec) => vec
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    self.inner_decoder
+                        .decode_owned(item)
+                        .map_err(|e| e.at_index(index))
+                })
+                .collect(),
+            _ => self.decode(&value),
+        }
+    }
+}
+
+/// Decodes a JSON object into any collection of `(String, V)` pairs, e.g. a
+/// `HashMap<String, V>`, `BTreeMap<String, V>` or `Vec<(String, V)>`.
+///
+/// Entries are visited in serde_json's map order: sorted by key by default, or
+/// in the order they were written if serde_json's `preserve_order` feature is
+/// enabled.  Use `key_value_pairs` for pairs that are always sorted.
+pub fn dict<'a, V, Collection>(
+    decoder: impl Decoder<'a, V> + 'a + Send + Sync,
+) -> BoxDecoder<'a, Collection>
+where
+    Collection: FromIterator<(String, V)> + 'a + Send + Sync,
+    V: 'a,
+{
+    Box::new(DictDecoder {
+        decode_key: Box::new(|key| Ok(key.to_string())),
+        value_decoder: decoder,
+        phantom: PhantomData,
+    })
+}
+
+/// Like `dict`, but also decodes each key using `key_decoder`.
+///
+/// The key decoder is first run against a JSON string containing the key.  If
+/// that fails and the key is exactly a JSON number or boolean (i.e. it would
+/// be written the same way as JSON, with no extra whitespace), the key decoder
+/// is retried against that value instead.  This means `integer()` &
+/// `boolean()` can be used to decode keys like `"1"` or `"true"`, while
+/// `string()` still decodes every key unchanged.
+pub fn dict_with_keys<'a, K, V, Collection>(
+    key_decoder: impl Decoder<'a, K> + 'a + Send + Sync,
+    value_decoder: impl Decoder<'a, V> + 'a + Send + Sync,
+) -> BoxDecoder<'a, Collection>
+where
+    Collection: FromIterator<(K, V)> + 'a + Send + Sync,
+    K: 'a,
+    V: 'a,
+{
+    Box::new(DictDecoder {
+        decode_key: Box::new(move |key| {
+            key_decoder
+                .decode(&serde_json::Value::String(key.to_string()))
+                .or_else(|error| match serde_json::from_str(key) {
+                    Ok(value @ serde_json::Value::Number(_))
+                    | Ok(value @ serde_json::Value::Bool(_))
+                        if serde_json::to_string(&value).ok().as_deref() == Some(key) =>
+                    {
+                        key_decoder.decode(&value)
+                    }
+                    _ => Err(error),
+                })
+                .map_err(|e| DecodeError::InvalidKey(Snippet::of_str(key), Box::new(e)))
+        }),
+        value_decoder,
+        phantom: PhantomData,
+    })
+}
+
+/// Decodes a JSON object into a list of key value pairs, sorted by key.
+///
+/// The pairs are sorted even if serde_json's `preserve_order` feature is
+/// enabled, so the order doesn't depend on how serde_json was built.
+pub fn key_value_pairs<'a, V>(
+    decoder: impl Decoder<'a, V> + 'a + Send + Sync,
+) -> BoxDecoder<'a, Vec<(String, V)>>
+where
+    V: 'a + Send + Sync,
+{
+    map(
+        |mut pairs: Vec<(String, V)>| {
+            pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+            pairs
+        },
+        dict(decoder),
+    )
+}
+
+type KeyDecodeFn<'a, K> = Box<dyn Fn(&str) -> Result<K, DecodeError> + 'a + Send + Sync>;
+
+pub struct DictDecoder<'a, K, V, D, DecodesTo: FromIterator<(K, V)>> {
+    decode_key: KeyDecodeFn<'a, K>,
+    value_decoder: D,
+    phantom: PhantomData<fn() -> (V, DecodesTo)>,
+}
+
+impl<'a, K, V, D, DecodesTo> Decoder<'a, DecodesTo> for DictDecoder<'a, K, V, D, DecodesTo>
+where
+    D: Decoder<'a, V>,
+    DecodesTo: FromIterator<(K, V)>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Object(map) => map
+                .iter()
+                .map(|(key, item)| {
+                    let decoded_key = (*self.decode_key)(key)?;
+                    let decoded_item = self
+                        .value_decoder
+                        .decode(item)
+                        .map_err(|e| e.in_field(key))?;
+                    Ok((decoded_key, decoded_item))
+                })
+                .collect(),
+            _ => Err(DecodeError::IncorrectType(
+                JsonType::Object,
+                Snippet::new(value),
+            )),
+        }
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut errors = Vec::new();
+                let collection = map
+                    .iter()
+                    .filter_map(|(key, item)| {
+                        let decoded_key = (*self.decode_key)(key).map_err(|e| errors.push(e));
+                        let decoded_item =
+                            self.value_decoder
+                                .decode_all_errors(item)
+                                .map_err(|item_errors| {
+                                    errors.extend(item_errors.into_iter().map(|e| e.in_field(key)))
+                                });
+                        Some((decoded_key.ok()?, decoded_item.ok()?))
+                    })
+                    .collect();
+                if errors.is_empty() {
+                    Ok(collection)
+                } else {
+                    Err(DecodeErrors::new(errors))
+                }
+            }
+            _ => Err(DecodeError::IncorrectType(JsonType::Object, Snippet::new(value)).into()),
+        }
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        match value {
+            serde_json::Value::Object(map) => map
+                .into_iter()
+                .map(|(key, item)| {
+                    let decoded_key = (*self.decode_key)(&key)?;
+                    let decoded_item = self
+                        .value_decoder
+                        .decode_owned(item)
+                        .map_err(|e| e.in_field(&key))?;
+                    Ok((decoded_key, decoded_item))
+                })
+                .collect(),
+            _ => self.decode(&value),
+        }
+    }
+}
+
+/// Tries each of the given decoders in order, returning the result of the first
+/// one that succeeds.
+///
+/// If every decoder fails the error will be a `DecodeError::OneOf` containing the
+/// error from each alternative.
+pub fn one_of<'a, T, D>(decoders: impl IntoIterator<Item = D>) -> BoxDecoder<'a, T>
+where
+    D: Decoder<'a, T> + 'a + Send + Sync,
+    T: 'a,
+{
+    Box::new(OneOfDecoder {
+        decoders: decoders.into_iter().collect(),
+    })
+}
+
+pub struct OneOfDecoder<D> {
+    pub(crate) decoders: Vec<D>,
+}
+
+impl<'a, DecodesTo, D> Decoder<'a, DecodesTo> for OneOfDecoder<D>
+where
+    D: Decoder<'a, DecodesTo>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        let mut errors = Vec::with_capacity(self.decoders.len());
+        for decoder in &self.decoders {
+            match decoder.decode(value) {
+                Ok(result) => return Ok(result),
+                Err(e) => errors.push(e),
+            }
+        }
+        Err(DecodeError::OneOf(errors))
+    }
 }
 
 // TODO: Do we need the lifetimes here
-pub fn map<'a, F, T1, NewDecodesTo>(func: F, d1: BoxDecoder<'a, T1>) -> BoxDecoder<'a, NewDecodesTo>
+pub fn map<'a, F, T1, NewDecodesTo>(
+    func: F,
+    d1: impl Decoder<'a, T1> + 'a + Send + Sync,
+) -> BoxDecoder<'a, NewDecodesTo>
 where
     F: (Fn(T1) -> NewDecodesTo) + 'a + Send + Sync,
     NewDecodesTo: 'a,
     T1: 'a,
 {
     Box::new(DecoderFn1 {
-        func: Box::new(func),
+        func,
+        decoder: d1,
+        phantom: PhantomData,
+    })
+}
+
+pub struct DecoderFn1<F, D, Argument1> {
+    pub(crate) func: F,
+    pub(crate) decoder: D,
+    pub(crate) phantom: PhantomData<fn() -> Argument1>,
+}
+
+impl<'a, DecodesTo, Argument1, F, D> Decoder<'a, DecodesTo> for DecoderFn1<F, D, Argument1>
+where
+    F: Fn(Argument1) -> DecodesTo,
+    D: Decoder<'a, Argument1>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        let arg0 = self.decoder.decode(value)?;
+        Ok((self.func)(arg0))
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        let arg0 = self.decoder.decode_all_errors(value)?;
+        Ok((self.func)(arg0))
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        let arg0 = self.decoder.decode_owned(value)?;
+        Ok((self.func)(arg0))
+    }
+}
+
+/// Transforms the decoded value with `func`, which may fail.
+///
+/// Errors from `func` can be any type that converts to a `DecodeError`.
+/// `String` & `&str` messages are returned as `DecodeError::Other`, while
+/// errors of your own type can be wrapped in `DecodeError::Custom` with
+/// `DecodeError::custom` and retrieved with `DecodeError::downcast_ref`.
+pub fn try_map<'a, F, T1, NewDecodesTo, E>(
+    func: F,
+    d1: impl Decoder<'a, T1> + 'a + Send + Sync,
+) -> BoxDecoder<'a, NewDecodesTo>
+where
+    F: (Fn(T1) -> Result<NewDecodesTo, E>) + 'a + Send + Sync,
+    E: Into<DecodeError>,
+    NewDecodesTo: 'a,
+    T1: 'a,
+{
+    Box::new(TryMapDecoder {
+        func,
         decoder: d1,
+        phantom: PhantomData,
     })
 }
 
-pub struct DecoderFn1<'a, DecodesTo, Argument1> {
-    func: Box<dyn Fn(Argument1) -> DecodesTo + 'a + Send + Sync>,
-    decoder: BoxDecoder<'a, Argument1>,
+pub struct TryMapDecoder<F, D, Argument1> {
+    pub(crate) func: F,
+    pub(crate) decoder: D,
+    pub(crate) phantom: PhantomData<fn() -> Argument1>,
 }
 
-impl<'a, DecodesTo, Argument1> Decoder<'a, DecodesTo> for DecoderFn1<'a, DecodesTo, Argument1> {
+impl<'a, DecodesTo, Argument1, F, D, E> Decoder<'a, DecodesTo> for TryMapDecoder<F, D, Argument1>
+where
+    F: Fn(Argument1) -> Result<DecodesTo, E>,
+    E: Into<DecodeError>,
+    D: Decoder<'a, Argument1>,
+{
     fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
         let arg0 = self.decoder.decode(value)?;
-        Ok((*self.func)(arg0))
+        (self.func)(arg0).map_err(Into::into)
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        let arg0 = self.decoder.decode_all_errors(value)?;
+        Ok((self.func)(arg0).map_err(Into::into)?)
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        let arg0 = self.decoder.decode_owned(value)?;
+        (self.func)(arg0).map_err(Into::into)
+    }
+}
+
+/// Transforms any error returned by `decoder` using `func`.
+pub fn map_err<'a, F, T>(
+    func: F,
+    decoder: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, T>
+where
+    F: (Fn(DecodeError) -> DecodeError) + 'a + Send + Sync,
+    T: 'a,
+{
+    Box::new(MapErrDecoder { func, decoder })
+}
+
+pub struct MapErrDecoder<F, D> {
+    pub(crate) func: F,
+    pub(crate) decoder: D,
+}
+
+impl<'a, DecodesTo, F, D> Decoder<'a, DecodesTo> for MapErrDecoder<F, D>
+where
+    F: Fn(DecodeError) -> DecodeError,
+    D: Decoder<'a, DecodesTo>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        self.decoder.decode(value).map_err(&self.func)
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        self.decoder
+            .decode_all_errors(value)
+            .map_err(|errors| errors.map(&self.func))
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        self.decoder.decode_owned(value).map_err(&self.func)
+    }
+}
+
+/// Decodes an internally tagged union, e.g. `{"type": "circle", "radius": 1}`.
+///
+/// Reads the string in `tag_field` and decodes the whole object with the
+/// decoder for that tag.
+pub fn tagged<'a, 't, T, D>(
+    tag_field: &str,
+    variants: impl IntoIterator<Item = (&'t str, D)>,
+) -> BoxDecoder<'a, T>
+where
+    D: Decoder<'a, T> + 'a + Send + Sync,
+    T: 'a,
+{
+    Box::new(TaggedDecoder::new(
+        TagStyle::Internal(tag_field.to_string()),
+        variants,
+    ))
+}
+
+/// Decodes an adjacently tagged union, e.g.
+/// `{"tag": "circle", "content": {"radius": 1}}`.
+///
+/// Reads the string in `tag_field` and decodes `content_field` with the
+/// decoder for that tag.  If `content_field` is missing the decoder is given
+/// `null` instead, so variants without any content (e.g. those decoded with
+/// `succeed`) can leave it out.
+pub fn adjacently_tagged<'a, 't, T, D>(
+    tag_field: &str,
+    content_field: &str,
+    variants: impl IntoIterator<Item = (&'t str, D)>,
+) -> BoxDecoder<'a, T>
+where
+    D: Decoder<'a, T> + 'a + Send + Sync,
+    T: 'a,
+{
+    Box::new(TaggedDecoder::new(
+        TagStyle::Adjacent(tag_field.to_string(), content_field.to_string()),
+        variants,
+    ))
+}
+
+/// Decodes an externally tagged union, e.g. `{"circle": {"radius": 1}}`.
+///
+/// The object must contain exactly one key, which is used as the tag.  The
+/// value of that key is decoded with the decoder for the tag.  A bare string
+/// such as `"empty"` is also accepted as a tag with no content, in which case
+/// the decoder for the tag is given `null`.
+pub fn externally_tagged<'a, 't, T, D>(
+    variants: impl IntoIterator<Item = (&'t str, D)>,
+) -> BoxDecoder<'a, T>
+where
+    D: Decoder<'a, T> + 'a + Send + Sync,
+    T: 'a,
+{
+    Box::new(TaggedDecoder::new(TagStyle::External, variants))
+}
+
+enum TagStyle {
+    Internal(String),
+    Adjacent(String, String),
+    External,
+}
+
+pub struct TaggedDecoder<D> {
+    style: TagStyle,
+    variants: Vec<(String, D)>,
+}
+
+impl<D> TaggedDecoder<D> {
+    fn new<'t>(style: TagStyle, variants: impl IntoIterator<Item = (&'t str, D)>) -> Self {
+        TaggedDecoder {
+            style,
+            variants: variants
+                .into_iter()
+                .map(|(tag, decoder)| (tag.to_string(), decoder))
+                .collect(),
+        }
+    }
+
+    fn variant(&self, tag: &str) -> Result<&D, DecodeError> {
+        self.variants
+            .iter()
+            .find(|(variant_tag, _)| variant_tag == tag)
+            .map(|(_, decoder)| decoder)
+            .ok_or_else(|| {
+                DecodeError::UnknownTag(
+                    Snippet::of_str(tag),
+                    self.variants.iter().map(|(tag, _)| tag.clone()).collect(),
+                )
+            })
+    }
+
+    fn read_tag(
+        &self,
+        map: &serde_json::Map<String, serde_json::Value>,
+        value: &serde_json::Value,
+        tag_field: &str,
+    ) -> Result<&D, DecodeError> {
+        let tag = map
+            .get(tag_field)
+            .ok_or_else(|| DecodeError::MissingField(tag_field.to_string(), Snippet::new(value)))?;
+        match tag {
+            serde_json::Value::String(tag) => self.variant(tag),
+            _ => Err(DecodeError::IncorrectType(
+                JsonType::String,
+                Snippet::new(tag),
+            )),
+        }
+        .map_err(|e| e.in_field(tag_field))
+    }
+
+    /// Finds the decoder for the tag in `value`, along with the value it
+    /// should decode.
+    fn dispatch<'d, 'v>(
+        &'d self,
+        value: &'v serde_json::Value,
+    ) -> Result<Dispatch<'d, 'v, D>, DecodeError>
+    where
+        'd: 'v,
+    {
+        let map = match (value, &self.style) {
+            (serde_json::Value::Object(map), _) => map,
+            (serde_json::Value::String(tag), TagStyle::External) => {
+                return Ok(Dispatch {
+                    decoder: self.variant(tag)?,
+                    content: &NULL,
+                    content_field: None,
+                    missing_content: Some(DecodeError::IncorrectType(
+                        JsonType::Object,
+                        Snippet::new(value),
+                    )),
+                })
+            }
+            _ => {
+                return Err(DecodeError::IncorrectType(
+                    JsonType::Object,
+                    Snippet::new(value),
+                ))
+            }
+        };
+        match &self.style {
+            TagStyle::Internal(tag_field) => Ok(Dispatch {
+                decoder: self.read_tag(map, value, tag_field)?,
+                content: value,
+                content_field: None,
+                missing_content: None,
+            }),
+            TagStyle::Adjacent(tag_field, content_field) => {
+                let decoder = self.read_tag(map, value, tag_field)?;
+                Ok(match map.get(content_field) {
+                    Some(content) => Dispatch {
+                        decoder,
+                        content,
+                        content_field: Some(content_field),
+                        missing_content: None,
+                    },
+                    None => Dispatch {
+                        decoder,
+                        content: &NULL,
+                        content_field: None,
+                        missing_content: Some(DecodeError::MissingField(
+                            content_field.clone(),
+                            Snippet::new(value),
+                        )),
+                    },
+                })
+            }
+            TagStyle::External => {
+                if map.len() != 1 {
+                    return Err(DecodeError::ExpectedSingleKey(map.len()));
+                }
+                let (tag, content) = map.iter().next().unwrap();
+                Ok(Dispatch {
+                    decoder: self.variant(tag).map_err(|e| e.in_field(tag))?,
+                    content,
+                    content_field: Some(tag),
+                    missing_content: None,
+                })
+            }
+        }
+    }
+}
+
+static NULL: serde_json::Value = serde_json::Value::Null;
+
+/// The decoder for a variant of a tagged union, along with the value it should
+/// decode.
+struct Dispatch<'d, 'v, D> {
+    decoder: &'d D,
+    content: &'v serde_json::Value,
+    /// The field `content` was read from, if it isn't the whole value.
+    content_field: Option<&'v str>,
+    /// Set if the value had no content, in which case `content` is `null` and
+    /// this error is returned if the variant can't be decoded from that.
+    missing_content: Option<DecodeError>,
+}
+
+impl<'d, 'v, D> Dispatch<'d, 'v, D> {
+    fn error(self, error: DecodeError) -> DecodeError {
+        match (self.missing_content, self.content_field) {
+            (Some(missing_content), _) => missing_content,
+            (None, Some(field_name)) => error.in_field(field_name),
+            (None, None) => error,
+        }
+    }
+
+    fn errors(self, errors: DecodeErrors) -> DecodeErrors {
+        match (self.missing_content, self.content_field) {
+            (Some(missing_content), _) => missing_content.into(),
+            (None, Some(field_name)) => errors.map(|e| e.in_field(field_name)),
+            (None, None) => errors,
+        }
+    }
+}
+
+impl<'a, DecodesTo, D> Decoder<'a, DecodesTo> for TaggedDecoder<D>
+where
+    D: Decoder<'a, DecodesTo>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        let dispatch = self.dispatch(value)?;
+        let result = dispatch.decoder.decode(dispatch.content);
+        result.map_err(|e| dispatch.error(e))
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        let dispatch = self.dispatch(value)?;
+        let result = dispatch.decoder.decode_all_errors(dispatch.content);
+        result.map_err(|errors| dispatch.errors(errors))
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        let dispatch = self.dispatch(&value)?;
+        if dispatch.missing_content.is_some() {
+            let result = dispatch.decoder.decode(dispatch.content);
+            return result.map_err(|e| dispatch.error(e));
+        }
+        let decoder = dispatch.decoder;
+        let content_field = dispatch.content_field.map(str::to_string);
+        let content = match (&content_field, value) {
+            (Some(field_name), serde_json::Value::Object(mut map)) => {
+                map.remove(field_name).unwrap_or_default()
+            }
+            (_, value) => value,
+        };
+        decoder
+            .decode_owned(content)
+            .map_err(|e| match &content_field {
+                Some(field_name) => e.in_field(field_name),
+                None => e,
+            })
+    }
+}
+
+/// Defers building a decoder until it is needed.
+///
+/// This makes it possible to write decoders for recursive data structures, e.g.
+/// `field("children", list(lazy(tree_decoder)))` inside `tree_decoder`.  The
+/// decoder is built each time a value is decoded.
+pub fn lazy<'a, F, T, D>(func: F) -> BoxDecoder<'a, T>
+where
+    F: (Fn() -> D) + 'a + Send + Sync,
+    D: Decoder<'a, T>,
+    T: 'a,
+{
+    Box::new(LazyDecoder { func })
+}
+
+pub struct LazyDecoder<F> {
+    pub(crate) func: F,
+}
+
+impl<'a, DecodesTo, F, D> Decoder<'a, DecodesTo> for LazyDecoder<F>
+where
+    F: Fn() -> D,
+    D: Decoder<'a, DecodesTo>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        (self.func)().decode(value)
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        (self.func)().decode_all_errors(value)
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        (self.func)().decode_owned(value)
     }
 }
 
@@ -275,7 +1462,7 @@ where
 }
 
 pub struct SerdeDecoder<T> {
-    phantom: PhantomData<T>,
+    pub(crate) phantom: PhantomData<T>,
 }
 
 impl<'a, DecodesTo> Decoder<'a, DecodesTo> for SerdeDecoder<DecodesTo>
@@ -283,8 +1470,11 @@ where
     for<'de> DecodesTo: serde::Deserialize<'de>,
 {
     fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
-        // TODO: Figure out if we can get rid of this clone somehow?
-        serde_json::from_value(value.clone()).map_err(|e| DecodeError::SerdeError(e.to_string()))
+        DecodesTo::deserialize(value).map_err(|e| DecodeError::SerdeError(e.to_string()))
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        serde_json::from_value(value).map_err(|e| DecodeError::SerdeError(e.to_string()))
     }
 }
 
@@ -296,9 +1486,12 @@ pub struct JsonDecoder {}
 
 impl<'a> Decoder<'a, serde_json::Value> for JsonDecoder {
     fn decode(&self, value: &serde_json::Value) -> Result<serde_json::Value, DecodeError> {
-        // TODO: Figure out if we can get rid of this clone somehow?
         Ok(value.clone())
     }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<serde_json::Value, DecodeError> {
+        Ok(value)
+    }
 }
 
 pub fn succeed<'a, V>(value: V) -> BoxDecoder<'a, V>
@@ -309,7 +1502,7 @@ where
 }
 
 pub struct SucceedDecoder<V> {
-    value: V,
+    pub(crate) value: V,
 }
 
 impl<'a, V> Decoder<'a, V> for SucceedDecoder<V>
@@ -322,16 +1515,41 @@ where
     }
 }
 
+/// A decoder that always succeeds with a value created by `func`.
+///
+/// Unlike `succeed` this doesn't need the value to implement `Clone`, and only
+/// creates it when the decoder is run.
+pub fn succeed_with<'a, F, V>(func: F) -> BoxDecoder<'a, V>
+where
+    F: (Fn() -> V) + 'a + Send + Sync,
+    V: 'a,
+{
+    Box::new(SucceedWithDecoder { func })
+}
+
+pub struct SucceedWithDecoder<F> {
+    pub(crate) func: F,
+}
+
+impl<'a, V, F> Decoder<'a, V> for SucceedWithDecoder<F>
+where
+    F: Fn() -> V,
+{
+    fn decode(&self, _value: &serde_json::Value) -> Result<V, DecodeError> {
+        Ok((self.func)())
+    }
+}
+
+/// A decoder that always fails with `DecodeError::Other`.  See `fail_with` for
+/// failing with an error of your own type.
 pub fn fail<V>(error: impl Into<String>) -> BoxDecoder<'static, V> {
-    // TODO: Might be nice to get this using a std::Error but it was
-    // surprisingly tricky.  For now just using a String instead.
     Box::new(FailDecoder {
         error: error.into(),
     })
 }
 
 pub struct FailDecoder {
-    error: String,
+    pub(crate) error: String,
 }
 
 impl<'a, V> Decoder<'a, V> for FailDecoder {
@@ -341,31 +1559,119 @@ impl<'a, V> Decoder<'a, V> for FailDecoder {
     }
 }
 
-pub fn and_then<'a, F, T, NewDecodesTo>(
+/// A decoder that always fails with `error`, which is converted to a
+/// `DecodeError` as in `try_map`.  Wrap errors of your own type with
+/// `DecodeError::custom` so they can be retrieved with
+/// `DecodeError::downcast_ref`.
+pub fn fail_with<'a, V>(error: impl Into<DecodeError>) -> BoxDecoder<'a, V> {
+    Box::new(FailWithDecoder {
+        error: error.into(),
+    })
+}
+
+pub struct FailWithDecoder {
+    pub(crate) error: DecodeError,
+}
+
+impl<'a, V> Decoder<'a, V> for FailWithDecoder {
+    fn decode(&self, _value: &serde_json::Value) -> Result<V, DecodeError> {
+        Err(self.error.clone())
+    }
+}
+
+pub fn and_then<'a, F, T, NewDecodesTo, D>(
     func: F,
-    d: BoxDecoder<'a, T>,
+    d: impl Decoder<'a, T> + 'a + Send + Sync,
 ) -> BoxDecoder<'a, NewDecodesTo>
 where
-    F: (Fn(T) -> BoxDecoder<'a, NewDecodesTo>) + 'a + Send + Sync,
+    F: (Fn(T) -> D) + 'a + Send + Sync,
+    D: Decoder<'a, NewDecodesTo>,
     NewDecodesTo: 'a,
     T: 'a,
 {
     Box::new(DecoderAndThen {
-        func: Box::new(func),
+        func,
         decoder: d,
+        phantom: PhantomData,
     })
 }
 
-pub struct DecoderAndThen<'a, DecodesTo, Argument> {
-    func: Box<dyn Fn(Argument) -> BoxDecoder<'a, DecodesTo> + 'a + Send + Sync>,
-    decoder: BoxDecoder<'a, Argument>,
+pub struct DecoderAndThen<F, D, Argument> {
+    pub(crate) func: F,
+    pub(crate) decoder: D,
+    pub(crate) phantom: PhantomData<fn() -> Argument>,
 }
 
-impl<'a, DecodesTo, Argument> Decoder<'a, DecodesTo> for DecoderAndThen<'a, DecodesTo, Argument> {
+impl<'a, DecodesTo, Argument, F, D, Inner> Decoder<'a, DecodesTo> for DecoderAndThen<F, D, Argument>
+where
+    F: Fn(Argument) -> Inner,
+    D: Decoder<'a, Argument>,
+    Inner: Decoder<'a, DecodesTo>,
+{
     fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
         let func_param = self.decoder.decode(value)?;
-        let inner_decoder = (*self.func)(func_param);
+        let inner_decoder = (self.func)(func_param);
         let res = inner_decoder.decode(value)?;
         Ok(res)
     }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        let func_param = self.decoder.decode_all_errors(value)?;
+        let inner_decoder = (self.func)(func_param);
+        inner_decoder.decode_all_errors(value)
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        // The first decoder only looks at the value, so the inner one can own it
+        let func_param = self.decoder.decode(&value)?;
+        let inner_decoder = (self.func)(func_param);
+        inner_decoder.decode_owned(value)
+    }
+}
+
+/// Like `and_then`, but rather than returning another decoder `func` is given
+/// the decoded value along with the JSON being decoded, and returns the result
+/// directly.
+///
+/// This avoids creating a new decoder for every value.  Decoders that `func`
+/// needs can be built once & captured, and their errors returned as is.
+/// Other errors are converted as in `try_map`.
+pub fn and_then_with<'a, F, T, NewDecodesTo, E>(
+    func: F,
+    d: impl Decoder<'a, T> + 'a + Send + Sync,
+) -> BoxDecoder<'a, NewDecodesTo>
+where
+    F: (Fn(T, &serde_json::Value) -> Result<NewDecodesTo, E>) + 'a + Send + Sync,
+    E: Into<DecodeError>,
+    NewDecodesTo: 'a,
+    T: 'a,
+{
+    Box::new(AndThenWithDecoder {
+        func,
+        decoder: d,
+        phantom: PhantomData,
+    })
+}
+
+pub struct AndThenWithDecoder<F, D, Argument> {
+    pub(crate) func: F,
+    pub(crate) decoder: D,
+    pub(crate) phantom: PhantomData<fn() -> Argument>,
+}
+
+impl<'a, DecodesTo, Argument, F, D, E> Decoder<'a, DecodesTo> for AndThenWithDecoder<F, D, Argument>
+where
+    F: Fn(Argument, &serde_json::Value) -> Result<DecodesTo, E>,
+    E: Into<DecodeError>,
+    D: Decoder<'a, Argument>,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        let func_param = self.decoder.decode(value)?;
+        (self.func)(func_param, value).map_err(Into::into)
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        let func_param = self.decoder.decode_all_errors(value)?;
+        Ok((self.func)(func_param, value).map_err(Into::into)?)
+    }
 }
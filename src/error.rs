@@ -0,0 +1,563 @@
+use crate::Snippet;
+use std::any::TypeId;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    #[error("Could not find field {0} in {1}")]
+    MissingField(String, Snippet),
+    #[error("Could not find index {0} in an array of length {1}")]
+    IndexOutOfBounds(usize, usize),
+    /// An array decoded as a tuple had the wrong number of elements.  Holds the
+    /// expected length, then the actual length.
+    #[error("Expected an array of length {0} but found one of length {1}")]
+    IncorrectLength(usize, usize),
+    /// The value was not of the expected kind.  The kind that was actually
+    /// found is available from `found`.
+    #[error("Expected a {0} but found a {1}")]
+    IncorrectType(JsonType, Snippet),
+    /// A number that was expected to be an integer had a fractional part or an
+    /// exponent, or was negative where an unsigned integer was expected.
+    #[error("Invalid integer: {0}")]
+    InvalidInteger(Snippet),
+    /// A number was outside the range of the type it was decoded into.
+    #[error("Number {0} is out of range")]
+    NumberOutOfRange(Snippet),
+    #[error("Serde error: {0}")]
+    SerdeError(String),
+    /// The input to `decode_str`, `decode_slice` or `decode_reader` was not valid JSON.
+    #[error("Invalid JSON at line {line} column {column}: {message}")]
+    InvalidJson {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    /// Reading the input to `decode_reader` failed.
+    #[error("IO error: {0}")]
+    Io(String),
+    #[error("Error: {0}")]
+    Other(String),
+    /// An error of the user's own type, from `fail_with` or `try_map`.  The
+    /// original error is the `source` of this one, and can be retrieved with
+    /// `DecodeError::downcast_ref`.
+    ///
+    /// Two `Custom` errors are equal if they are of the same type and their
+    /// `Display` & `Debug` output match.  The type isn't known for errors that
+    /// were converted from a `Box<dyn Error>`, so these are compared by their
+    /// output alone.  Use `downcast_ref` to compare the errors themselves.
+    #[error("{0}")]
+    Custom(#[source] CustomError),
+    /// The key of an object could not be decoded by the key decoder given to
+    /// `dict_with_keys`.  Holds a snippet of the key as a JSON string.
+    #[error("Invalid key {0}: {1}")]
+    InvalidKey(Snippet, Box<DecodeError>),
+    /// The tag of a tagged union did not match any of the known tags.  Holds a
+    /// snippet of the tag as a JSON string, then the known tags.
+    #[error("Unknown tag {0}, expected one of: {}", .1.join(", "))]
+    UnknownTag(Snippet, Vec<String>),
+    /// An externally tagged union did not have exactly one key.
+    #[error("Expected an object with a single key but found {0} keys")]
+    ExpectedSingleKey(usize),
+    /// An error that occurred while decoding the named field of an object.
+    #[error("at {}: {}", self.path_string(), self.root_cause())]
+    Field(String, Box<DecodeError>),
+    /// An error that occurred while decoding an element of an array.
+    #[error("at {}: {}", self.path_string(), self.root_cause())]
+    Index(usize, Box<DecodeError>),
+    /// Every alternative given to `one_of` failed.  Contains the error from each
+    /// alternative, in the order they were tried.
+    #[error("None of the alternatives matched: {}", join_errors(.0))]
+    OneOf(Vec<DecodeError>),
+    /// An error from decoding text, along with the position of the value that
+    /// failed to decode.  Lines & columns start at 1, and columns count bytes
+    /// as they do for `InvalidJson`.
+    #[error("{error} at line {line} column {column}")]
+    Located {
+        line: usize,
+        column: usize,
+        error: Box<DecodeError>,
+    },
+}
+
+fn join_errors(errors: &[DecodeError]) -> String {
+    errors
+        .iter()
+        .enumerate()
+        .map(|(i, e)| format!("({}) {}", i + 1, e))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// An error of the user's own type, carried by `DecodeError::Custom`.
+///
+/// This dereferences to the original error.  It is reference counted so that
+/// decoders like `fail_with` can return the same error many times.
+#[derive(Clone)]
+pub struct CustomError {
+    error: Arc<dyn Error + Send + Sync>,
+    /// The type of `error`, if it was known when this was created
+    type_id: Option<TypeId>,
+}
+
+impl CustomError {
+    /// Returns the original error if it is of type `E`
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref()
+    }
+}
+
+// CustomError deliberately doesn't implement `Error` itself: this way
+// `DecodeError::source` returns the original error, rather than a wrapper around
+// it, so the error chain is kept intact & downcasting works as expected.
+impl std::ops::Deref for CustomError {
+    type Target = dyn Error + Send + Sync;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.error
+    }
+}
+
+impl fmt::Debug for CustomError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.error, f)
+    }
+}
+
+impl fmt::Display for CustomError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+/// Custom errors needn't implement `PartialEq`, so they are compared by their
+/// type along with their `Debug` & `Display` output instead.  Errors whose type
+/// isn't known are compared by their output alone.
+impl PartialEq for CustomError {
+    fn eq(&self, other: &Self) -> bool {
+        let same_type = match (self.type_id, other.type_id) {
+            (Some(type_id), Some(other_type_id)) => type_id == other_type_id,
+            _ => true,
+        };
+        Arc::ptr_eq(&self.error, &other.error)
+            || (same_type
+                && self.to_string() == other.to_string()
+                && format!("{:?}", self) == format!("{:?}", other))
+    }
+}
+
+/// Plain messages become `DecodeError::Other`.
+impl From<String> for DecodeError {
+    fn from(message: String) -> Self {
+        DecodeError::Other(message)
+    }
+}
+
+/// Plain messages become `DecodeError::Other`.
+impl From<&str> for DecodeError {
+    fn from(message: &str) -> Self {
+        DecodeError::Other(message.to_string())
+    }
+}
+
+/// Boxed errors become `DecodeError::Custom`, unless they are `DecodeError`s.
+impl From<Box<dyn Error + Send + Sync>> for DecodeError {
+    fn from(error: Box<dyn Error + Send + Sync>) -> Self {
+        match error.downcast::<DecodeError>() {
+            Ok(error) => *error,
+            Err(error) => DecodeError::Custom(error.into()),
+        }
+    }
+}
+
+impl From<Box<dyn Error + Send + Sync>> for CustomError {
+    fn from(error: Box<dyn Error + Send + Sync>) -> Self {
+        CustomError {
+            error: Arc::from(error),
+            type_id: None,
+        }
+    }
+}
+
+/// The kinds of value that can appear in JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonType {
+    Null,
+    Boolean,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonType {
+    /// Returns the kind of `value`
+    pub fn of(value: &serde_json::Value) -> Self {
+        use serde_json::Value;
+
+        match value {
+            Value::Null => JsonType::Null,
+            Value::Bool(_) => JsonType::Boolean,
+            Value::Number(_) => JsonType::Number,
+            Value::String(_) => JsonType::String,
+            Value::Array(_) => JsonType::Array,
+            Value::Object(_) => JsonType::Object,
+        }
+    }
+
+    /// A stable, lower case name for this kind, e.g. `"number"`.
+    pub fn code(self) -> &'static str {
+        match self {
+            JsonType::Null => "null",
+            JsonType::Boolean => "boolean",
+            JsonType::Number => "number",
+            JsonType::String => "string",
+            JsonType::Array => "array",
+            JsonType::Object => "object",
+        }
+    }
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            JsonType::Null => "Null",
+            JsonType::Boolean => "Boolean",
+            JsonType::Number => "Number",
+            JsonType::String => "String",
+            JsonType::Array => "Array",
+            JsonType::Object => "Object",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single step on the way from the top level JSON value to the value that
+/// failed to decode.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl DecodeError {
+    /// Creates an error from one of the user's own errors, wrapping it in
+    /// `DecodeError::Custom`.  If `error` is already a `DecodeError` it is
+    /// returned as is.
+    ///
+    /// Use this to return your own errors from `try_map` & `fail_with`, e.g.
+    /// `.try_map(|s| s.parse::<u32>().map_err(DecodeError::custom))`.
+    pub fn custom<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        match (Box::new(error) as Box<dyn Error + Send + Sync>).downcast::<DecodeError>() {
+            Ok(error) => *error,
+            Err(error) => DecodeError::Custom(CustomError {
+                error: Arc::from(error),
+                type_id: Some(TypeId::of::<E>()),
+            }),
+        }
+    }
+
+    /// Returns the user's own error if this was caused by one of type `E`.
+    ///
+    /// This looks through any path information, so it works for errors that
+    /// occurred within fields & arrays.
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        match self.root_cause() {
+            DecodeError::Custom(error) => error.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the path from the top level value to the value that failed to decode.
+    ///
+    /// The path is empty if the error occurred at the top level.
+    pub fn path(&self) -> Vec<PathSegment> {
+        let mut path = Vec::new();
+        let mut error = self;
+        loop {
+            match error {
+                DecodeError::Field(name, inner) => {
+                    path.push(PathSegment::Field(name.clone()));
+                    error = inner;
+                }
+                DecodeError::Index(index, inner) => {
+                    path.push(PathSegment::Index(*index));
+                    error = inner;
+                }
+                DecodeError::Located { error: inner, .. } => error = inner,
+                _ => return path,
+            }
+        }
+    }
+
+    /// Returns the underlying error, without any of the path information.
+    pub fn root_cause(&self) -> &DecodeError {
+        match self {
+            DecodeError::Field(_, inner)
+            | DecodeError::Index(_, inner)
+            | DecodeError::Located { error: inner, .. } => inner.root_cause(),
+            other => other,
+        }
+    }
+
+    /// Renders the path to this error in a form like `.a[3].b`
+    pub fn path_string(&self) -> String {
+        self.path().iter().map(ToString::to_string).collect()
+    }
+
+    /// A stable identifier for the kind of error, e.g. `"incorrect_type"`.
+    ///
+    /// Errors nested within a field or index have the code of their root cause,
+    /// so these can be matched on without digging through the path.  Codes will
+    /// not change between releases, unlike the messages from `Display`.
+    pub fn code(&self) -> &'static str {
+        match self.root_cause() {
+            DecodeError::MissingField(..) => "missing_field",
+            DecodeError::IndexOutOfBounds(..) => "index_out_of_bounds",
+            DecodeError::IncorrectLength(..) => "incorrect_length",
+            DecodeError::IncorrectType(..) => "incorrect_type",
+            DecodeError::InvalidInteger(..) => "invalid_integer",
+            DecodeError::NumberOutOfRange(..) => "number_out_of_range",
+            DecodeError::SerdeError(..) => "serde_error",
+            DecodeError::InvalidJson { .. } => "invalid_json",
+            DecodeError::Io(..) => "io_error",
+            DecodeError::Other(..) => "other",
+            DecodeError::Custom(..) => "custom",
+            DecodeError::InvalidKey(..) => "invalid_key",
+            DecodeError::UnknownTag(..) => "unknown_tag",
+            DecodeError::ExpectedSingleKey(..) => "expected_single_key",
+            DecodeError::OneOf(..) => "one_of",
+            DecodeError::Field(..) | DecodeError::Index(..) | DecodeError::Located { .. } => {
+                unreachable!("root_cause never returns a path or location")
+            }
+        }
+    }
+
+    /// The kind of value that was expected, for `IncorrectType` errors.
+    pub fn expected(&self) -> Option<JsonType> {
+        match self.root_cause() {
+            DecodeError::IncorrectType(expected, _) => Some(*expected),
+            _ => None,
+        }
+    }
+
+    /// The kind of value that was actually found, for `IncorrectType`,
+    /// `InvalidInteger` & `NumberOutOfRange` errors.
+    pub fn found(&self) -> Option<JsonType> {
+        match self.root_cause() {
+            DecodeError::IncorrectType(_, snippet)
+            | DecodeError::InvalidInteger(snippet)
+            | DecodeError::NumberOutOfRange(snippet) => Some(snippet.kind()),
+            _ => None,
+        }
+    }
+
+    /// A preview of the value that failed to decode, if the error has one.
+    pub fn snippet(&self) -> Option<&Snippet> {
+        match self.root_cause() {
+            DecodeError::IncorrectType(_, snippet)
+            | DecodeError::MissingField(_, snippet)
+            | DecodeError::InvalidInteger(snippet)
+            | DecodeError::NumberOutOfRange(snippet) => Some(snippet),
+            _ => None,
+        }
+    }
+
+    /// Renders the path to this error as a JSON pointer (RFC 6901), like
+    /// `/a/3/b`.  The pointer is empty if the error occurred at the top level.
+    pub fn pointer(&self) -> String {
+        self.path()
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Field(name) => {
+                    format!("/{}", name.replace('~', "~0").replace('/', "~1"))
+                }
+                PathSegment::Index(index) => format!("/{}", index),
+            })
+            .collect()
+    }
+
+    /// Returns the line & column of the value that failed to decode, if known.
+    ///
+    /// This is only available for errors from `decode_str`, `decode_slice` &
+    /// `decode_reader`.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            DecodeError::Located { line, column, .. } => Some((*line, *column)),
+            DecodeError::InvalidJson { line, column, .. } => Some((*line, *column)),
+            _ => None,
+        }
+    }
+
+    /// Attaches the position of the failed value within `json`, the text that
+    /// was decoded.
+    pub(crate) fn locate_in(self, json: &[u8]) -> Self {
+        match crate::location::locate(json, &self.path()) {
+            Some((line, column)) => DecodeError::Located {
+                line,
+                column,
+                error: Box::new(self),
+            },
+            None => self,
+        }
+    }
+
+    pub(crate) fn from_parse_error(error: serde_json::Error) -> Self {
+        if error.is_io() {
+            return DecodeError::Io(error.to_string());
+        }
+        // serde_json includes the position at the end of the message, but we store
+        // it separately.
+        let message = error.to_string();
+        let suffix = format!(" at line {} column {}", error.line(), error.column());
+        DecodeError::InvalidJson {
+            message: message.trim_end_matches(&suffix).to_string(),
+            line: error.line(),
+            column: error.column(),
+        }
+    }
+
+    pub(crate) fn in_field(self, field_name: &str) -> Self {
+        DecodeError::Field(field_name.to_string(), Box::new(self))
+    }
+
+    pub(crate) fn at_index(self, index: usize) -> Self {
+        DecodeError::Index(index, Box::new(self))
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, ".{}", name),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+/// A collection of every error found while decoding a value.
+///
+/// Returned by `Decoder::decode_all_errors`.  This always contains at least one
+/// error.
+#[derive(Debug, PartialEq)]
+pub struct DecodeErrors(Vec<DecodeError>);
+
+impl DecodeErrors {
+    pub(crate) fn new(errors: Vec<DecodeError>) -> Self {
+        debug_assert!(!errors.is_empty(), "DecodeErrors should never be empty");
+        DecodeErrors(errors)
+    }
+
+    /// The first error that was found
+    pub fn first(&self) -> &DecodeError {
+        &self.0[0]
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, DecodeError> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_vec(self) -> Vec<DecodeError> {
+        self.0
+    }
+
+    pub(crate) fn map(self, func: impl Fn(DecodeError) -> DecodeError) -> Self {
+        DecodeErrors(self.0.into_iter().map(func).collect())
+    }
+}
+
+impl From<DecodeError> for DecodeErrors {
+    fn from(error: DecodeError) -> Self {
+        DecodeErrors(vec![error])
+    }
+}
+
+impl IntoIterator for DecodeErrors {
+    type Item = DecodeError;
+    type IntoIter = std::vec::IntoIter<DecodeError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a DecodeErrors {
+    type Item = &'a DecodeError;
+    type IntoIter = std::slice::Iter<'a, DecodeError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for DecodeErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DecodeErrors {}
+
+/// Errors serialize as an object for returning to clients, e.g.
+///
+/// ```json
+/// {
+///   "path": "/items/3/price",
+///   "code": "incorrect_type",
+///   "message": "Expected a Number but found a \"3\"",
+///   "expected": "number",
+///   "found": "string"
+/// }
+/// ```
+///
+/// `path` is a JSON pointer (see `DecodeError::pointer`), `code` is from
+/// `DecodeError::code` and `message` describes the root cause.  `expected` &
+/// `found` are only present for `incorrect_type` errors, `line` & `column`
+/// only for errors from decoding text, and `alternatives` only for `one_of`
+/// errors.  The paths of alternatives are relative to the `one_of`.
+impl serde::Serialize for DecodeError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let root_cause = self.root_cause();
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("path", &self.pointer())?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("message", &root_cause.to_string())?;
+        if let (Some(expected), Some(found)) = (self.expected(), self.found()) {
+            map.serialize_entry("expected", expected.code())?;
+            map.serialize_entry("found", found.code())?;
+        }
+        if let Some((line, column)) = self.location() {
+            map.serialize_entry("line", &line)?;
+            map.serialize_entry("column", &column)?;
+        }
+        if let DecodeError::OneOf(alternatives) = root_cause {
+            map.serialize_entry("alternatives", alternatives)?;
+        }
+        map.end()
+    }
+}
+
+/// Serializes as an array of errors, each in the form described on
+/// `DecodeError`.
+impl serde::Serialize for DecodeErrors {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{decoders, BoxDecoder, DecodeError, Decoder};
+
+/// Methods for chaining decoders together, so that decoders can be read left
+/// to right rather than inside out.
+///
+/// `field("tags", string().list().optional())` is equivalent to
+/// `field("tags", option(list::<_, Vec<_>>(string())))`.
+pub trait DecoderExt<'a, T> {
+    /// Transforms the decoded value with `func`.  See `map`.
+    fn map<F, U>(self, func: F) -> BoxDecoder<'a, U>
+    where
+        F: (Fn(T) -> U) + 'a + Send + Sync,
+        U: 'a;
+
+    /// Transforms the decoded value with `func`, which may fail.  See `try_map`.
+    fn try_map<F, U, E>(self, func: F) -> BoxDecoder<'a, U>
+    where
+        F: (Fn(T) -> Result<U, E>) + 'a + Send + Sync,
+        E: Into<DecodeError>,
+        U: 'a;
+
+    /// Chooses another decoder to run based on the decoded value.  See `and_then`.
+    fn and_then<F, U, D>(self, func: F) -> BoxDecoder<'a, U>
+    where
+        F: (Fn(T) -> D) + 'a + Send + Sync,
+        D: Decoder<'a, U>,
+        U: 'a;
+
+    /// Runs `func` on the decoded value & the JSON being decoded.  See
+    /// `and_then_with`.
+    fn and_then_with<F, U, E>(self, func: F) -> BoxDecoder<'a, U>
+    where
+        F: (Fn(T, &serde_json::Value) -> Result<U, E>) + 'a + Send + Sync,
+        E: Into<DecodeError>,
+        U: 'a;
+
+    /// Decodes `null` as `None`.  See `option`.
+    fn optional(self) -> BoxDecoder<'a, Option<T>>
+    where
+        T: Send + Sync;
+
+    /// Decodes an array of values into a `Vec`.  See `list`.
+    fn list(self) -> BoxDecoder<'a, Vec<T>>
+    where
+        T: Send + Sync;
+
+    /// Decodes the named field of an object with this decoder.  See `field`.
+    fn field(self, field_name: &str) -> BoxDecoder<'a, T>;
+
+    /// Transforms any error with `func`.  See `map_err`.
+    fn map_err<F>(self, func: F) -> BoxDecoder<'a, T>
+    where
+        F: (Fn(DecodeError) -> DecodeError) + 'a + Send + Sync;
+
+    /// Tries `other` if this decoder fails.  See `one_of`.
+    fn or(self, other: impl Decoder<'a, T> + 'a + Send + Sync) -> BoxDecoder<'a, T>;
+}
+
+impl<'a, T, This> DecoderExt<'a, T> for This
+where
+    This: Decoder<'a, T> + 'a + Send + Sync,
+    T: 'a,
+{
+    fn map<F, U>(self, func: F) -> BoxDecoder<'a, U>
+    where
+        F: (Fn(T) -> U) + 'a + Send + Sync,
+        U: 'a,
+    {
+        decoders::map(func, self)
+    }
+
+    fn try_map<F, U, E>(self, func: F) -> BoxDecoder<'a, U>
+    where
+        F: (Fn(T) -> Result<U, E>) + 'a + Send + Sync,
+        E: Into<DecodeError>,
+        U: 'a,
+    {
+        decoders::try_map(func, self)
+    }
+
+    fn and_then<F, U, D>(self, func: F) -> BoxDecoder<'a, U>
+    where
+        F: (Fn(T) -> D) + 'a + Send + Sync,
+        D: Decoder<'a, U>,
+        U: 'a,
+    {
+        decoders::and_then(func, self)
+    }
+
+    fn and_then_with<F, U, E>(self, func: F) -> BoxDecoder<'a, U>
+    where
+        F: (Fn(T, &serde_json::Value) -> Result<U, E>) + 'a + Send + Sync,
+        E: Into<DecodeError>,
+        U: 'a,
+    {
+        decoders::and_then_with(func, self)
+    }
+
+    fn optional(self) -> BoxDecoder<'a, Option<T>>
+    where
+        T: Send + Sync,
+    {
+        decoders::option(self)
+    }
+
+    fn list(self) -> BoxDecoder<'a, Vec<T>>
+    where
+        T: Send + Sync,
+    {
+        decoders::list(self)
+    }
+
+    fn field(self, field_name: &str) -> BoxDecoder<'a, T> {
+        decoders::field(field_name, self)
+    }
+
+    fn map_err<F>(self, func: F) -> BoxDecoder<'a, T>
+    where
+        F: (Fn(DecodeError) -> DecodeError) + 'a + Send + Sync,
+    {
+        decoders::map_err(func, self)
+    }
+
+    fn or(self, other: impl Decoder<'a, T> + 'a + Send + Sync) -> BoxDecoder<'a, T> {
+        decoders::one_of(vec![self.boxed(), other.boxed()])
+    }
+}
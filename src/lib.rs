@@ -1,11 +1,31 @@
+mod decode;
 mod decoders;
+mod error;
+mod ext;
+mod location;
 mod map_fns;
+mod pipeline;
+mod render;
+mod snippet;
+pub mod typed;
 
+pub use decode::{decoder, Decode};
 pub use decoders::{
-    and_then, boolean, fail, field, float, integer, json, list, map, option, serde, string,
-    succeed, unsigned_integer, BoxDecoder,
+    adjacently_tagged, and_then, and_then_with, at, boolean, dict, dict_with_keys, exact_length,
+    externally_tagged, fail, fail_with, field, field_or, field_or_default, float, index, integer,
+    json, key_value_pairs, lazy, list, map, map_err, null, one_of, option, optional_field,
+    patch_field, serde, string, succeed, succeed_with, tagged, try_map, unsigned_integer,
+    with_default, ArcDecoder, BoxDecoder, Patch,
 };
+pub use error::{CustomError, DecodeError, DecodeErrors, JsonType, PathSegment};
+pub use ext::DecoderExt;
 pub use map_fns::*;
+pub use pipeline::{decode_with, Pipeline};
+pub use render::error_to_string;
+pub use snippet::{with_snippet_limit, Snippet};
+
+#[cfg(feature = "derive")]
+pub use json_decode_derive::JsonDecode;
 
 pub trait Decoder<'a, DecodesTo> {
     // OK, so theoretically this needs to store some functions & some collection of arguments.
@@ -16,22 +36,114 @@ pub trait Decoder<'a, DecodesTo> {
     //
     // Or alternatively all functions have to take a JSON.Value enum and do the decoding based on that.
     fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError>;
+
+    /// Decodes a value, reporting every failure rather than stopping at the first.
+    ///
+    /// Decoders that are built from other decoders (e.g. `field`, `list` & the
+    /// `mapN` functions) keep going after a failure, so this returns every
+    /// error along with its path.  This is useful for validating forms or API
+    /// requests.
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        self.decode(value).map_err(DecodeErrors::from)
+    }
+
+    /// Decodes a value, taking ownership of it.
+    ///
+    /// Decoders that produce owned data (e.g. `string`, `json` & `serde`) move
+    /// it out of `value` rather than cloning it, which avoids doubling the
+    /// memory used by large strings & arrays.  Decoders that hand a single part
+    /// of `value` to another decoder (e.g. `field`, `index`, `list`, `dict`,
+    /// `option`, `tagged` & `and_then`) pass that part on by value.
+    ///
+    /// Moving stops at decoders that run several decoders against the same
+    /// value: the `mapN` & `try_mapN` functions, `decode_with` pipelines and
+    /// `one_of`, which may need to retry.  These decode a reference to
+    /// `value`, so anything beneath them is cloned as with `decode`.
+    ///
+    /// This is used by `decode_str`, `decode_slice` & `decode_reader`.
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        self.decode(&value)
+    }
+
+    /// Parses a string of JSON and decodes it.
+    ///
+    /// Syntax errors are returned as `DecodeError::InvalidJson`.  Decode errors
+    /// are wrapped in `DecodeError::Located`, giving the line & column of the
+    /// value that failed to decode.
+    fn decode_str(&self, json: &str) -> Result<DecodesTo, DecodeError> {
+        self.decode_slice(json.as_bytes())
+    }
+
+    /// Parses a slice of JSON bytes and decodes it.  See `decode_str`.
+    fn decode_slice(&self, json: &[u8]) -> Result<DecodesTo, DecodeError> {
+        let value = serde_json::from_slice(json).map_err(DecodeError::from_parse_error)?;
+        self.decode_owned(value).map_err(|e| e.locate_in(json))
+    }
+
+    /// Reads JSON from `reader`, parses it and decodes it.  See `decode_str`.
+    fn decode_reader<R>(&self, mut reader: R) -> Result<DecodesTo, DecodeError>
+    where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        let mut json = Vec::new();
+        reader
+            .read_to_end(&mut json)
+            .map_err(|e| DecodeError::Io(e.to_string()))?;
+        self.decode_slice(&json)
+    }
+
+    /// Boxes this decoder, erasing its type.  This is mostly useful for the
+    /// decoders in `typed`, whose types can't be named.
+    fn boxed(self) -> BoxDecoder<'a, DecodesTo>
+    where
+        Self: Sized + Send + Sync + 'a,
+    {
+        Box::new(self)
+    }
+
+    /// Turns this decoder into an `ArcDecoder`, which can be cloned & reused
+    /// without rebuilding it.
+    fn shared(self) -> ArcDecoder<'a, DecodesTo>
+    where
+        Self: Sized + Send + Sync + 'a,
+    {
+        std::sync::Arc::new(self)
+    }
 }
 
-#[derive(thiserror::Error, Debug, PartialEq)]
-pub enum DecodeError {
-    #[error("Could not find field {0} in {1}")]
-    MissingField(String, String),
-    #[error("Expected a {0} but found a {1}")]
-    IncorrectType(String, String),
-    #[error("Invalid integer: {0}")]
-    InvalidInteger(String),
-    #[error("Integer {0} was too big to decode as {1}")]
-    IntegerOverflow(String, &'static str),
-    #[error("Serde error: {0}")]
-    SerdeError(String),
-    #[error("Error: {0}")]
-    Other(String),
+impl<'a, DecodesTo, D> Decoder<'a, DecodesTo> for Box<D>
+where
+    D: Decoder<'a, DecodesTo> + ?Sized,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        (**self).decode(value)
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        (**self).decode_all_errors(value)
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        (**self).decode_owned(value)
+    }
+}
+
+impl<'a, DecodesTo, D> Decoder<'a, DecodesTo> for std::sync::Arc<D>
+where
+    D: Decoder<'a, DecodesTo> + ?Sized,
+{
+    fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        (**self).decode(value)
+    }
+
+    fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+        (**self).decode_all_errors(value)
+    }
+
+    fn decode_owned(&self, value: serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        (**self).decode_owned(value)
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +248,20 @@ mod tests {
         assert_eq!(decoder.decode(&serde_json::json!(null)), Ok(None))
     }
 
+    #[test]
+    fn decoding_null() {
+        let decoder = null("nothing");
+
+        assert_eq!(decoder.decode(&serde_json::json!(null)), Ok("nothing"));
+        assert_eq!(
+            decoder.decode(&serde_json::json!(0)),
+            Err(DecodeError::IncorrectType(
+                JsonType::Null,
+                Snippet::new(&serde_json::json!(0))
+            ))
+        );
+    }
+
     #[test]
     fn decode_using_serde() {}
 
@@ -170,7 +296,9 @@ mod tests {
         assert_eq!(integer().decode(&serde_json::json!(1)), Ok(1 as i64));
         assert_eq!(
             integer::<i8>().decode(&serde_json::json!(512)),
-            Err(DecodeError::IntegerOverflow("512".to_string(), "i8"))
+            Err(DecodeError::NumberOutOfRange(Snippet::new(
+                &serde_json::json!(512)
+            )))
         );
 
         assert_eq!(
@@ -183,7 +311,1310 @@ mod tests {
         );
         assert_eq!(
             unsigned_integer::<u8>().decode(&serde_json::json!(512)),
-            Err(DecodeError::IntegerOverflow("512".to_string(), "u8"))
+            Err(DecodeError::NumberOutOfRange(Snippet::new(
+                &serde_json::json!(512)
+            )))
+        );
+    }
+
+    #[test]
+    fn errors_include_the_path_to_the_failure() {
+        let decoder = field("a", list::<_, Vec<i64>>(field("b", integer())));
+
+        let error = decoder
+            .decode(&serde_json::json!({"a": [{"b": 1}, {"b": 2}, {"b": "three"}]}))
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            DecodeError::Field(
+                "a".into(),
+                Box::new(DecodeError::Index(
+                    2,
+                    Box::new(DecodeError::Field(
+                        "b".into(),
+                        Box::new(DecodeError::IncorrectType(
+                            JsonType::Number,
+                            Snippet::new(&serde_json::json!("three"))
+                        ))
+                    ))
+                ))
+            )
+        );
+        assert_eq!(
+            error.path(),
+            vec![
+                PathSegment::Field("a".into()),
+                PathSegment::Index(2),
+                PathSegment::Field("b".into())
+            ]
+        );
+        assert_eq!(
+            error.to_string(),
+            "at .a[2].b: Expected a Number but found a \"three\""
+        );
+    }
+
+    #[test]
+    fn decode_all_errors_reports_every_failure() {
+        let decoder = map3(
+            |name: String, age: u32, tags: Vec<String>| (name, age, tags),
+            field("name", string()),
+            field("age", unsigned_integer()),
+            field("tags", list(string())),
+        );
+
+        let json = serde_json::json!({"age": -1, "tags": ["ok", 1, false]});
+
+        let errors = decoder.decode_all_errors(&json).unwrap_err();
+        assert_eq!(
+            errors.iter().map(|e| e.path_string()).collect::<Vec<_>>(),
+            vec!["", ".age", ".tags[1]", ".tags[2]"]
+        );
+        assert_eq!(
+            errors.first(),
+            &DecodeError::MissingField("name".into(), Snippet::new(&json))
+        );
+        assert_eq!(errors.len(), 4);
+
+        assert_eq!(
+            decoder.decode(&json),
+            Err(DecodeError::MissingField(
+                "name".into(),
+                Snippet::new(&json)
+            ))
+        );
+    }
+
+    #[test]
+    fn one_of_tries_each_decoder() {
+        let decoder = one_of(vec![
+            integer(),
+            and_then(
+                |s: String| match s.parse() {
+                    Ok(i) => succeed(i),
+                    Err(_) => fail("Not a number"),
+                },
+                string(),
+            ),
+        ]);
+
+        assert_eq!(decoder.decode(&serde_json::json!(1)), Ok(1i64));
+        assert_eq!(decoder.decode(&serde_json::json!("2")), Ok(2i64));
+
+        let error = decoder.decode(&serde_json::json!(true)).unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::OneOf(vec![
+                DecodeError::IncorrectType(
+                    JsonType::Number,
+                    Snippet::new(&serde_json::json!(true))
+                ),
+                DecodeError::IncorrectType(
+                    JsonType::String,
+                    Snippet::new(&serde_json::json!(true))
+                ),
+            ])
+        );
+        assert_eq!(
+            error.to_string(),
+            "None of the alternatives matched: (1) Expected a Number but found a true, (2) Expected a String but found a true"
+        );
+    }
+
+    #[test]
+    fn decoding_nested_fields_with_at() {
+        let decoder = at(&["data", "user", "name"], string());
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"data": {"user": {"name": "Graeme"}}})),
+            Ok("Graeme".to_string())
+        );
+
+        let error = decoder
+            .decode(&serde_json::json!({"data": {"account": {}}}))
+            .unwrap_err();
+        assert_eq!(error.path(), vec![PathSegment::Field("data".into())]);
+        assert_eq!(
+            error.root_cause(),
+            &DecodeError::MissingField(
+                "user".into(),
+                Snippet::new(&serde_json::json!({"account": {}}))
+            )
+        );
+    }
+
+    #[test]
+    fn decoding_array_elements_with_index() {
+        let decoder = index(1, string());
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!(["one", "two"])),
+            Ok("two".to_string())
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!(["one"])),
+            Err(DecodeError::IndexOutOfBounds(1, 1))
+        );
+        assert_eq!(
+            decoder
+                .decode(&serde_json::json!(["one", 2]))
+                .unwrap_err()
+                .path(),
+            vec![PathSegment::Index(1)]
+        );
+    }
+
+    #[test]
+    fn decoding_dicts() {
+        let json = serde_json::json!({"one": 1, "two": 2});
+
+        assert_eq!(
+            dict::<_, std::collections::HashMap<_, _>>(integer()).decode(&json),
+            Ok(vec![("one".to_string(), 1), ("two".to_string(), 2)]
+                .into_iter()
+                .collect())
+        );
+        assert_eq!(
+            key_value_pairs(integer()).decode(&json),
+            Ok(vec![("one".to_string(), 1), ("two".to_string(), 2)])
+        );
+        assert_eq!(
+            key_value_pairs(integer()).decode(&serde_json::json!({"b": 1, "a": 2})),
+            Ok(vec![("a".to_string(), 2), ("b".to_string(), 1)])
+        );
+
+        let error = dict::<_, std::collections::BTreeMap<_, i64>>(integer())
+            .decode(&serde_json::json!({"one": 1, "two": "2"}))
+            .unwrap_err();
+        assert_eq!(error.path(), vec![PathSegment::Field("two".into())]);
+    }
+
+    #[test]
+    fn decoding_dict_keys() {
+        assert_eq!(
+            dict_with_keys::<_, _, std::collections::BTreeMap<_, _>>(integer::<u32>(), string())
+                .decode(&serde_json::json!({"1": "one", "2": "two"})),
+            Ok(vec![(1, "one".to_string()), (2, "two".to_string())]
+                .into_iter()
+                .collect())
+        );
+        assert_eq!(
+            dict_with_keys::<_, _, std::collections::BTreeMap<_, _>>(boolean(), integer::<i64>())
+                .decode(&serde_json::json!({"true": 1, "false": 0})),
+            Ok(vec![(false, 0), (true, 1)].into_iter().collect())
+        );
+        assert_eq!(
+            dict_with_keys::<_, _, Vec<_>>(string(), integer::<i64>())
+                .decode(&serde_json::json!({"1": 1})),
+            Ok(vec![("1".to_string(), 1)])
+        );
+        assert_eq!(
+            dict_with_keys::<_, _, Vec<(u32, String)>>(integer(), string())
+                .decode(&serde_json::json!({"one": "one"})),
+            Err(DecodeError::InvalidKey(
+                Snippet::new(&serde_json::json!("one")),
+                Box::new(DecodeError::IncorrectType(
+                    JsonType::Number,
+                    Snippet::new(&serde_json::json!("one"))
+                ))
+            ))
+        );
+        assert_eq!(
+            dict_with_keys::<_, _, Vec<(u32, String)>>(integer(), string())
+                .decode(&serde_json::json!({" 1 ": "one"})),
+            Err(DecodeError::InvalidKey(
+                Snippet::new(&serde_json::json!(" 1 ")),
+                Box::new(DecodeError::IncorrectType(
+                    JsonType::Number,
+                    Snippet::new(&serde_json::json!(" 1 "))
+                ))
+            ))
+        );
+        assert_eq!(
+            dict_with_keys::<_, _, Vec<(u8, String)>>(integer(), string())
+                .decode(&serde_json::json!({"300": "three hundred"})),
+            Err(DecodeError::InvalidKey(
+                Snippet::new(&serde_json::json!("300")),
+                Box::new(DecodeError::NumberOutOfRange(Snippet::new(
+                    &serde_json::json!(300)
+                )))
+            ))
+        );
+    }
+
+    #[test]
+    fn decoding_optional_fields() {
+        let decoder = optional_field("name", string());
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"name": "Graeme"})),
+            Ok(Some("Graeme".to_string()))
+        );
+        assert_eq!(decoder.decode(&serde_json::json!({})), Ok(None));
+        assert_eq!(
+            decoder
+                .decode(&serde_json::json!({"name": null}))
+                .unwrap_err()
+                .path(),
+            vec![PathSegment::Field("name".into())]
+        );
+    }
+
+    #[test]
+    fn decoding_patch_fields() {
+        let decoder = patch_field("name", string());
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"name": "Graeme"})),
+            Ok(Patch::Value("Graeme".to_string()))
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"name": null})),
+            Ok(Patch::Null)
+        );
+        assert_eq!(decoder.decode(&serde_json::json!({})), Ok(Patch::Absent));
+    }
+
+    #[test]
+    fn decoding_fields_with_defaults() {
+        let decoder = map2(
+            |retries: u32, hosts: Vec<String>| (retries, hosts),
+            field_or("retries", unsigned_integer(), 3),
+            field_or_default("hosts", list(string())),
+        );
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"retries": 5, "hosts": ["a"]})),
+            Ok((5, vec!["a".to_string()]))
+        );
+        assert_eq!(decoder.decode(&serde_json::json!({})), Ok((3, vec![])));
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"retries": null, "hosts": null})),
+            Ok((3, vec![]))
+        );
+        assert_eq!(
+            decoder
+                .decode(&serde_json::json!({"retries": "5"}))
+                .unwrap_err()
+                .path(),
+            vec![PathSegment::Field("retries".into())]
+        );
+
+        let decoder = with_default(0, integer::<i32>());
+        assert_eq!(decoder.decode(&serde_json::json!(null)), Ok(0));
+        assert_eq!(decoder.decode(&serde_json::json!(10)), Ok(10));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Tree {
+        value: i64,
+        children: Vec<Tree>,
+    }
+
+    impl Tree {
+        fn new(value: i64, children: Vec<Tree>) -> Self {
+            Tree { value, children }
+        }
+    }
+
+    fn tree_decoder() -> BoxDecoder<'static, Tree> {
+        map2(
+            Tree::new,
+            field("value", integer()),
+            field("children", list(lazy(tree_decoder))),
+        )
+    }
+
+    #[test]
+    fn decoding_recursive_structures_with_lazy() {
+        let mut json = serde_json::json!({"value": 0, "children": []});
+        let mut expected = Tree::new(0, vec![]);
+        for value in 1..100 {
+            json = serde_json::json!({"value": value, "children": [json, {"value": -1, "children": []}]});
+            expected = Tree::new(value, vec![expected, Tree::new(-1, vec![])]);
+        }
+
+        assert_eq!(tree_decoder().decode(&json), Ok(expected));
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Shape {
+        Circle(f64),
+        Square(f64),
+    }
+
+    fn shape_variants() -> Vec<(&'static str, BoxDecoder<'static, Shape>)> {
+        vec![
+            ("circle", map(Shape::Circle, field("radius", float()))),
+            ("square", map(Shape::Square, field("side", float()))),
+        ]
+    }
+
+    #[test]
+    fn decoding_tagged_unions() {
+        let decoder = tagged("type", shape_variants());
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"type": "circle", "radius": 1.0})),
+            Ok(Shape::Circle(1.0))
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"type": "square", "side": 2.0})),
+            Ok(Shape::Square(2.0))
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"type": "triangle"})),
+            Err(DecodeError::Field(
+                "type".into(),
+                Box::new(DecodeError::UnknownTag(
+                    Snippet::new(&serde_json::json!("triangle")),
+                    vec!["circle".into(), "square".into()]
+                ))
+            ))
+        );
+        assert_eq!(
+            decoder
+                .decode(&serde_json::json!({"radius": 1.0}))
+                .unwrap_err()
+                .root_cause(),
+            &DecodeError::MissingField(
+                "type".into(),
+                Snippet::new(&serde_json::json!({"radius": 1.0}))
+            )
+        );
+    }
+
+    #[test]
+    fn decoding_adjacently_tagged_unions() {
+        let decoder = adjacently_tagged("tag", "content", shape_variants());
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"tag": "circle", "content": {"radius": 1.0}})),
+            Ok(Shape::Circle(1.0))
+        );
+        assert_eq!(
+            decoder
+                .decode(&serde_json::json!({"tag": "square", "content": {}}))
+                .unwrap_err()
+                .path(),
+            vec![PathSegment::Field("content".into())]
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"tag": "circle"})),
+            Err(DecodeError::MissingField(
+                "content".into(),
+                Snippet::new(&serde_json::json!({"tag": "circle"}))
+            ))
+        );
+
+        let point = map(|()| Shape::Circle(0.0), succeed(()));
+        let decoder = adjacently_tagged("tag", "content", vec![("point", point)]);
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"tag": "point"})),
+            Ok(Shape::Circle(0.0))
+        );
+    }
+
+    #[test]
+    fn decoding_externally_tagged_unions() {
+        let decoder = externally_tagged(shape_variants());
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"square": {"side": 2.0}})),
+            Ok(Shape::Square(2.0))
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"circle": {}, "square": {}})),
+            Err(DecodeError::ExpectedSingleKey(2))
+        );
+        assert_eq!(
+            decoder
+                .decode(&serde_json::json!({"hexagon": {}}))
+                .unwrap_err()
+                .to_string(),
+            "at .hexagon: Unknown tag \"hexagon\", expected one of: circle, square"
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!("circle")),
+            Err(DecodeError::IncorrectType(
+                JsonType::Object,
+                Snippet::new(&serde_json::json!("circle"))
+            ))
+        );
+
+        let point = map(|()| Shape::Circle(0.0), succeed(()));
+        let decoder = externally_tagged(vec![("point", point)]);
+        assert_eq!(
+            decoder.decode(&serde_json::json!("point")),
+            Ok(Shape::Circle(0.0))
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!("hexagon")),
+            Err(DecodeError::UnknownTag(
+                Snippet::new(&serde_json::json!("hexagon")),
+                vec!["point".into()]
+            ))
+        );
+    }
+
+    #[test]
+    fn chaining_decoders_with_decoder_ext() {
+        let decoder = string()
+            .list()
+            .optional()
+            .map(|tags| tags.unwrap_or_default().len())
+            .field("tags");
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"tags": ["a", "b"]})),
+            Ok(2)
+        );
+        assert_eq!(decoder.decode(&serde_json::json!({"tags": null})), Ok(0));
+
+        let decoder = integer::<i64>()
+            .map(|i| i.to_string())
+            .or(string())
+            .and_then(|s| {
+                if s.is_empty() {
+                    fail("Empty")
+                } else {
+                    succeed(s)
+                }
+            })
+            .map_err(|e| DecodeError::Other(format!("Bad id: {}", e)));
+
+        assert_eq!(decoder.decode(&serde_json::json!(1)), Ok("1".to_string()));
+        assert_eq!(
+            decoder.decode(&serde_json::json!("abc")),
+            Ok("abc".to_string())
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!("")),
+            Err(DecodeError::Other("Bad id: Error: Empty".into()))
+        );
+    }
+
+    #[test]
+    fn decoding_a_struct_with_a_pipeline() {
+        let decoder = decode_with(|field_one| {
+            move |field_two| {
+                move |field_three| {
+                    move |field_four| Test4Struct {
+                        field_one,
+                        field_two,
+                        field_three,
+                        field_four,
+                    }
+                }
+            }
+        })
+        .required("field_one", string())
+        .required_at(&["nested", "field_two"], integer())
+        .optional("field_three", boolean(), false)
+        .hardcoded(1.0)
+        .build();
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"field_one": "test", "nested": {"field_two": 10}})),
+            Ok(Test4Struct {
+                field_one: "test".to_string(),
+                field_two: 10,
+                field_three: false,
+                field_four: 1.0
+            })
+        );
+
+        let errors = decoder
+            .decode_all_errors(&serde_json::json!({"field_one": 1, "field_three": "no"}))
+            .unwrap_err();
+        assert_eq!(
+            errors.iter().map(|e| e.path_string()).collect::<Vec<_>>(),
+            vec![".field_one", "", ".field_three"]
+        );
+    }
+
+    #[test]
+    fn decoding_with_the_decode_trait() {
+        assert_eq!(
+            decoder::<Vec<Option<u8>>>().decode(&serde_json::json!([1, null])),
+            Ok(vec![Some(1), None])
+        );
+        assert_eq!(
+            decoder::<std::collections::HashMap<String, (String, f32)>>()
+                .decode(&serde_json::json!({"a": ["b", 1.5]})),
+            Ok(vec![("a".to_string(), ("b".to_string(), 1.5))]
+                .into_iter()
+                .collect())
+        );
+        assert_eq!(
+            <(i64, bool)>::decoder()
+                .decode(&serde_json::json!([1, "true"]))
+                .unwrap_err()
+                .path(),
+            vec![PathSegment::Index(1)]
+        );
+        assert_eq!(
+            <(i64, bool)>::decoder().decode(&serde_json::json!([1, true, 2])),
+            Err(DecodeError::IncorrectLength(2, 3))
+        );
+        assert_eq!(
+            <(i64, bool)>::decoder()
+                .decode(&serde_json::json!([1]))
+                .unwrap_err()
+                .to_string(),
+            "Expected an array of length 2 but found one of length 1"
+        );
+    }
+
+    #[test]
+    fn decoding_wide_and_narrow_numbers_with_the_decode_trait() {
+        assert_eq!(
+            decoder::<i128>().decode(&serde_json::json!(18446744073709551615u64)),
+            Ok(18446744073709551615)
+        );
+        assert_eq!(
+            decoder::<i128>().decode(&serde_json::json!(-9223372036854775808i64)),
+            Ok(-9223372036854775808)
+        );
+        assert_eq!(
+            decoder::<u128>().decode(&serde_json::json!(18446744073709551615u64)),
+            Ok(18446744073709551615)
+        );
+        assert_eq!(decoder::<f32>().decode(&serde_json::json!(1.5)), Ok(1.5));
+        assert_eq!(
+            decoder::<f32>().decode(&serde_json::json!(1e300)),
+            Err(DecodeError::NumberOutOfRange(Snippet::new(
+                &serde_json::json!(1e300)
+            )))
         );
     }
+
+    #[test]
+    fn decoding_from_strings_slices_and_readers() {
+        let decoder = field("field_one", string());
+        let json = r#"{"field_one": "test"}"#;
+
+        assert_eq!(decoder.decode_str(json), Ok("test".to_string()));
+        assert_eq!(
+            decoder.decode_slice(json.as_bytes()),
+            Ok("test".to_string())
+        );
+        assert_eq!(
+            decoder.decode_reader(std::io::Cursor::new(json)),
+            Ok("test".to_string())
+        );
+
+        assert_eq!(
+            decoder.decode_str("{\n  \"field_one\": @\n}"),
+            Err(DecodeError::InvalidJson {
+                message: "expected value".into(),
+                line: 2,
+                column: 16
+            })
+        );
+    }
+
+    #[test]
+    fn decode_str_reports_the_location_of_errors() {
+        let decoder = field(
+            "items",
+            list::<_, Vec<u32>>(field("price", unsigned_integer())),
+        );
+        let json = r#"{
+            "other": {"price": "ignored", "items": []},
+            "items": [
+                {"price": 1},
+                {"name": "[\"}", "price": "2"}
+            ]
+        }"#;
+
+        let error = decoder.decode_str(json).unwrap_err();
+        assert_eq!(error.location(), Some((5, 43)));
+        assert_eq!(error.path_string(), ".items[1].price");
+        assert_eq!(
+            error.to_string(),
+            "at .items[1].price: Expected a Number but found a \"2\" at line 5 column 43"
+        );
+
+        let error = decoder.decode_str(r#"{"items": [{}]}"#).unwrap_err();
+        assert_eq!(error.location(), Some((1, 12)));
+
+        let decoder = field("a", string());
+        let error = decoder.decode_str(r#"{"é": 1, "a": 2}"#).unwrap_err();
+        assert_eq!(error.location(), Some((1, 16)));
+        assert_eq!(
+            decoder.decode_str(r#"{"é": 1, "a": @}"#),
+            Err(DecodeError::InvalidJson {
+                message: "expected value".into(),
+                line: 1,
+                column: 16
+            })
+        );
+    }
+
+    #[test]
+    fn errors_only_keep_a_snippet_of_the_value() {
+        let big_object: serde_json::Map<_, _> = (0..10_000)
+            .map(|i| (format!("field_{}", i), serde_json::json!("x".repeat(100))))
+            .collect();
+        let json = serde_json::Value::Object(big_object);
+
+        let error = field("missing", string()).decode(&json).unwrap_err();
+        assert!(error.to_string().len() < 200, "{}", error);
+
+        let snippet = Snippet::with_limit(&serde_json::json!(["abcdef", [1, 2, 3]]), 10);
+        assert!(snippet.is_truncated());
+        assert_eq!(snippet.to_string(), r#"["abcde…",…]"#);
+        assert_eq!(snippet.preview(), &serde_json::json!(["abcde…"]));
+
+        let many_keys: serde_json::Map<_, _> = (0..40)
+            .map(|i| (format!("k{:02}", i), serde_json::json!(i)))
+            .collect();
+        let snippet = Snippet::with_limit(&serde_json::Value::Object(many_keys), 20);
+        assert!(snippet.is_truncated());
+        assert_eq!(snippet.to_string(), r#"{"k00":0,"k01":1,…}"#);
+        assert_eq!(
+            snippet.to_pretty_string(),
+            "{\n  \"k00\": 0,\n  \"k01\": 1,\n  …\n}"
+        );
+
+        let snippet = Snippet::with_limit(&serde_json::json!([[1, 2, 3], 4]), 6);
+        assert_eq!(snippet.to_string(), "[[1,…],…]");
+        let snippet = Snippet::with_limit(&serde_json::json!([[], {}]), 100);
+        assert!(!snippet.is_truncated());
+        assert_eq!(snippet.to_string(), "[[],{}]");
+        assert_eq!(snippet.to_pretty_string(), "[\n  [],\n  {}\n]");
+
+        let huge_key = "k".repeat(1_000_000);
+        let json = serde_json::json!({ huge_key.clone(): 1 });
+        let error = field("missing", string()).decode(&json).unwrap_err();
+        assert!(error.to_string().len() < 200, "{}", error);
+        assert_eq!(error.snippet().map(Snippet::is_truncated), Some(true));
+
+        let error = tagged("type", vec![("a", succeed(()))])
+            .decode(&serde_json::json!({ "type": huge_key.clone() }))
+            .unwrap_err();
+        assert!(error.to_string().len() < 200, "{}", error);
+        let error = dict_with_keys::<_, _, Vec<(i64, i64)>>(integer(), integer())
+            .decode(&json)
+            .unwrap_err();
+        assert!(error.to_string().len() < 400, "{}", error);
+
+        let snippet = Snippet::with_limit(&serde_json::json!({"a": [1, 2]}), 100);
+        assert!(!snippet.is_truncated());
+        assert_eq!(snippet.to_string(), r#"{"a":[1,2]}"#);
+
+        let snippet = Snippet::with_limit(&serde_json::json!({"a": "bcdef", "g": 1}), 11);
+        assert!(snippet.is_truncated());
+        assert_eq!(snippet.to_string(), r#"{"a":"bc…",…}"#);
+
+        let json = serde_json::json!({"abcdefgh": 1, "i": 2});
+        let decoder = field("missing", string());
+        let error = with_snippet_limit(4, || decoder.decode(&json)).unwrap_err();
+        assert_eq!(error.snippet().map(Snippet::is_truncated), Some(true));
+        let error = decoder.decode(&json).unwrap_err();
+        assert_eq!(error.snippet().map(Snippet::is_truncated), Some(false));
+
+        for empty in [
+            serde_json::json!({}),
+            serde_json::json!([]),
+            serde_json::json!(""),
+        ] {
+            let json = serde_json::Value::Array(vec![empty; 100_000]);
+            let snippet = Snippet::new(&json);
+            assert!(snippet.is_truncated());
+            assert!(snippet.to_string().len() < 200, "{}", snippet);
+
+            let error = string().decode(&json).unwrap_err();
+            assert!(error.to_string().len() < 300, "{}", error);
+        }
+    }
+
+    #[test]
+    fn errors_expose_structured_details() {
+        let decoder = field("a", list::<_, Vec<i64>>(integer()));
+
+        let error = decoder
+            .decode(&serde_json::json!({"a": [1, "two"]}))
+            .unwrap_err();
+        assert_eq!(error.code(), "incorrect_type");
+        assert_eq!(error.expected(), Some(JsonType::Number));
+        assert_eq!(error.found(), Some(JsonType::String));
+        assert_eq!(
+            error.snippet().unwrap().preview(),
+            &serde_json::json!("two")
+        );
+
+        let error = decoder.decode(&serde_json::json!({})).unwrap_err();
+        assert_eq!(error.code(), "missing_field");
+        assert_eq!(error.expected(), None);
+        assert_eq!(error.found(), None);
+        assert_eq!(JsonType::Object.code(), "object");
+
+        let error = decoder
+            .decode(&serde_json::json!({"a": [1.5]}))
+            .unwrap_err();
+        assert_eq!(error.code(), "invalid_integer");
+        assert_eq!(error.found(), Some(JsonType::Number));
+        assert_eq!(error.snippet().unwrap().preview(), &serde_json::json!(1.5));
+    }
+
+    #[test]
+    fn errors_serialize_for_clients() {
+        let decoder = field("items", list::<_, Vec<f64>>(field("price", float())));
+
+        let errors = decoder
+            .decode_all_errors(&serde_json::json!({"items": [{"price": 1}, {"price": "2"}, {}]}))
+            .unwrap_err();
+
+        assert_eq!(
+            serde_json::to_value(&errors).unwrap(),
+            serde_json::json!([
+                {
+                    "path": "/items/1/price",
+                    "code": "incorrect_type",
+                    "message": "Expected a Number but found a \"2\"",
+                    "expected": "number",
+                    "found": "string"
+                },
+                {
+                    "path": "/items/2",
+                    "code": "missing_field",
+                    "message": "Could not find field price in {}"
+                }
+            ])
+        );
+
+        let error = field("a/b", integer::<i64>()).decode_str("{}").unwrap_err();
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "path": "",
+                "code": "missing_field",
+                "message": "Could not find field a/b in {}",
+                "line": 1,
+                "column": 1
+            })
+        );
+        assert_eq!(
+            DecodeError::Field("a/b".into(), Box::new(DecodeError::Other("x".into()))).pointer(),
+            "/a~1b"
+        );
+    }
+
+    #[test]
+    fn error_to_string_renders_a_detailed_message() {
+        let decoder = field("items", list::<_, Vec<f64>>(field("price", float())));
+
+        let error = decoder
+            .decode_str(r#"{"items": [{"price": 1}, {"price": {"amount": 2}}]}"#)
+            .unwrap_err();
+        assert_eq!(
+            error_to_string(&error),
+            "Problem with the value at json.items[1].price (line 1, column 36):\n\
+             \n    {\n      \"amount\": 2\n    }\n\
+             \nExpected a Number"
+        );
+
+        let decoder = field(
+            "id",
+            one_of(vec![integer().map(|i: i64| i.to_string()), string()]),
+        );
+        let error = decoder
+            .decode(&serde_json::json!({"id": true}))
+            .unwrap_err();
+        assert_eq!(
+            error_to_string(&error),
+            "None of the alternatives for the value at json.id matched, \
+             they failed in the following 2 ways:\n\
+             \n(1) Problem with the value at json.id:\n\
+             \n        true\n\
+             \n    Expected a Number\n\
+             \n(2) Problem with the value at json.id:\n\
+             \n        true\n\
+             \n    Expected a String"
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct BadId(String);
+
+    impl std::fmt::Display for BadId {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "Bad id: {}", self.0)
+        }
+    }
+
+    impl std::error::Error for BadId {}
+
+    #[test]
+    fn custom_errors_can_be_downcast() {
+        use std::error::Error;
+
+        let decoder = field(
+            "id",
+            string().try_map(|id| {
+                if id.starts_with("id_") {
+                    Ok(id)
+                } else {
+                    Err(DecodeError::custom(BadId(id)))
+                }
+            }),
+        );
+
+        let error = decoder
+            .decode(&serde_json::json!({"id": "nope"}))
+            .unwrap_err();
+        assert_eq!(error.code(), "custom");
+        assert_eq!(error.downcast_ref::<BadId>(), Some(&BadId("nope".into())));
+        assert_eq!(error.to_string(), "at .id: Bad id: nope");
+        assert!(error
+            .root_cause()
+            .source()
+            .unwrap()
+            .downcast_ref::<BadId>()
+            .is_some());
+
+        let decoder = fail_with::<String>(DecodeError::custom(BadId("x".into())));
+        let error = decoder.decode(&serde_json::json!(null)).unwrap_err();
+        assert_eq!(error.downcast_ref::<BadId>(), Some(&BadId("x".into())));
+        assert_eq!(decoder.decode(&serde_json::json!(null)).unwrap_err(), error);
+
+        assert_eq!(
+            fail_with::<String>(DecodeError::custom(BadId("x".into())))
+                .decode(&serde_json::json!(null)),
+            Err(error)
+        );
+        assert_ne!(
+            fail_with::<String>(DecodeError::custom(BadId("y".into())))
+                .decode(&serde_json::json!(null)),
+            decoder.decode(&serde_json::json!(null))
+        );
+
+        let error = try_map(|_: i64| Err::<i64, _>("not today"), integer())
+            .decode(&serde_json::json!(1))
+            .unwrap_err();
+        assert_eq!(error, DecodeError::Other("not today".into()));
+        assert_eq!(error.code(), "other");
+        assert_eq!(
+            fail_with::<()>(format!("not {}", "today")).decode(&serde_json::json!(1)),
+            Err(DecodeError::Other("not today".into()))
+        );
+
+        // Only the type decides what is a message, not how an error looks
+        #[derive(Debug)]
+        struct Quoted;
+
+        impl std::fmt::Display for Quoted {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "Quoted")
+            }
+        }
+
+        impl std::error::Error for Quoted {}
+
+        let error = fail_with::<()>(DecodeError::custom(Quoted))
+            .decode(&serde_json::json!(1))
+            .unwrap_err();
+        assert_eq!(error.code(), "custom");
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(BadId("z".into()));
+        let error = fail_with::<()>(boxed)
+            .decode(&serde_json::json!(1))
+            .unwrap_err();
+        assert_eq!(error.downcast_ref::<BadId>(), Some(&BadId("z".into())));
+
+        // Errors of different types aren't equal, even if they look the same
+        #[derive(Debug)]
+        struct Impostor(String);
+
+        impl std::fmt::Display for Impostor {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "Bad id: {}", self.0)
+            }
+        }
+
+        impl std::error::Error for Impostor {}
+
+        let bad_id = DecodeError::custom(BadId("z".into()));
+        let impostor = DecodeError::custom(Impostor("z".into()));
+        assert_eq!(bad_id.to_string(), impostor.to_string());
+        assert_eq!(
+            format!("{:?}", bad_id),
+            format!("{:?}", impostor).replace("Impostor", "BadId")
+        );
+        assert_ne!(bad_id, impostor);
+        assert_eq!(bad_id, DecodeError::custom(BadId("z".into())));
+        // The type of a boxed error isn't known, so only its output is compared
+        assert_eq!(error, bad_id);
+    }
+
+    #[test]
+    fn try_map_functions_report_errors_at_the_current_path() {
+        let range = try_map2(
+            |start: i64, end: i64| {
+                if start <= end {
+                    Ok((start, end))
+                } else {
+                    Err(format!("{} is after {}", start, end))
+                }
+            },
+            field("start", integer()),
+            field("end", integer()),
+        );
+        let decoder = field("ranges", list::<_, Vec<_>>(range));
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"ranges": [{"start": 1, "end": 2}]})),
+            Ok(vec![(1, 2)])
+        );
+
+        let error = decoder
+            .decode(
+                &serde_json::json!({"ranges": [{"start": 1, "end": 2}, {"start": 3, "end": 2}]}),
+            )
+            .unwrap_err();
+        assert_eq!(error.to_string(), "at .ranges[1]: Error: 3 is after 2");
+        assert_eq!(error.code(), "other");
+
+        let address = "127.0.0.1".parse::<std::net::IpAddr>().unwrap();
+        assert_eq!(
+            string()
+                .try_map(|s| s.parse::<std::net::IpAddr>().map_err(DecodeError::custom))
+                .decode(&serde_json::json!("127.0.0.1")),
+            Ok(address)
+        );
+
+        // DecodeErrors are passed through as is, rather than wrapped
+        let error = try_map(
+            |_: String| Err::<(), _>(DecodeError::Other("nope".into())),
+            string(),
+        )
+        .decode(&serde_json::json!("x"))
+        .unwrap_err();
+        assert_eq!(error, DecodeError::Other("nope".into()));
+    }
+
+    #[test]
+    fn decoders_can_produce_values_that_are_not_clone() {
+        struct Buffer(Vec<u8>);
+
+        let buffer = succeed_with(|| Buffer(Vec::with_capacity(16)));
+        assert_eq!(
+            buffer
+                .decode(&serde_json::json!(null))
+                .unwrap()
+                .0
+                .capacity(),
+            16
+        );
+
+        let circle = field("radius", float()).map(|radius: f64| radius * radius);
+        let square = field("side", float()).map(|side: f64| side * side);
+        let decoder =
+            field("shape", string()).and_then_with(move |shape, value| match shape.as_str() {
+                "circle" => circle.decode(value),
+                "square" => square.decode(value),
+                _ => Err(DecodeError::Other(format!("Unknown shape {}", shape))),
+            });
+
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"shape": "square", "side": 2.0})),
+            Ok(4.0)
+        );
+        assert_eq!(
+            decoder.decode(&serde_json::json!({"shape": "circle", "diameter": 2.0})),
+            Err(DecodeError::MissingField(
+                "radius".into(),
+                Snippet::new(&serde_json::json!({"shape": "circle", "diameter": 2.0}))
+            ))
+        );
+    }
+
+    #[test]
+    fn typed_decoders_can_be_mixed_with_boxed_ones() {
+        let item = typed::map3(
+            |name: String, price: f64, tags: Vec<String>| (name, price, tags),
+            typed::field("name", typed::string()),
+            typed::field("price", typed::float()),
+            typed::field("tags", list(string())),
+        );
+        let decoder = typed::field("items", typed::list::<_, Vec<_>, _>(item)).boxed();
+
+        assert_eq!(
+            decoder.decode(
+                &serde_json::json!({"items": [{"name": "a", "price": 1.5, "tags": ["x"]}]})
+            ),
+            Ok(vec![("a".to_string(), 1.5, vec!["x".to_string()])])
+        );
+
+        let errors = decoder
+            .decode_all_errors(
+                &serde_json::json!({"items": [{"name": 1, "price": "2", "tags": []}]}),
+            )
+            .unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(DecodeError::path_string)
+                .collect::<Vec<_>>(),
+            vec![".items[0].name", ".items[0].price"]
+        );
+
+        let parsed = typed::try_map(
+            |s: String| s.parse::<i64>().map_err(DecodeError::custom),
+            typed::string(),
+        );
+        assert_eq!(parsed.decode(&serde_json::json!("12")), Ok(12));
+        assert_eq!(
+            parsed.decode(&serde_json::json!("x")).unwrap_err().code(),
+            "custom"
+        );
+
+        let count = typed::one_of([
+            typed::at(&["data", "count"], typed::integer::<i64>()),
+            typed::at(&["count"], typed::integer::<i64>()),
+        ]);
+        assert_eq!(
+            count.decode(&serde_json::json!({"data": {"count": 3}})),
+            Ok(3)
+        );
+        assert_eq!(count.decode(&serde_json::json!({"count": 4})), Ok(4));
+        let sized = typed::and_then(
+            |length: usize| {
+                typed::field(
+                    "items",
+                    typed::exact_length(length, typed::list(typed::integer::<i64>())),
+                )
+            },
+            typed::field_or("length", typed::integer(), 0),
+        );
+        assert_eq!(
+            sized.decode(&serde_json::json!({"length": 1, "items": [1]})),
+            Ok(vec![1])
+        );
+        assert_eq!(
+            sized
+                .decode(&serde_json::json!({"items": [1]}))
+                .unwrap_err()
+                .root_cause(),
+            &DecodeError::IncorrectLength(0, 1)
+        );
+        assert_eq!(
+            typed::exact_length(2, typed::list::<_, Vec<i64>, _>(typed::integer()))
+                .decode(&serde_json::json!([1])),
+            Err(DecodeError::IncorrectLength(2, 1))
+        );
+        assert_eq!(
+            typed::with_default(0, typed::lazy(typed::integer::<i64>))
+                .decode(&serde_json::json!(null)),
+            Ok(0)
+        );
+        assert_eq!(
+            typed::field_or_default("tags", typed::list::<_, Vec<String>, _>(typed::string()))
+                .decode(&serde_json::json!({})),
+            Ok(vec![])
+        );
+        assert_eq!(typed::null(()).decode(&serde_json::json!(null)), Ok(()));
+    }
+
+    #[test]
+    fn shared_decoders_can_be_reused() {
+        let user = field("name", string()).shared();
+        let decoder = map2(
+            |author, editor| (author, editor),
+            field("author", user.clone()),
+            field("editor", user.clone()),
+        )
+        .shared();
+
+        let json = serde_json::json!({"author": {"name": "a"}, "editor": {"name": "b"}});
+        let handle = {
+            let decoder = decoder.clone();
+            let json = json.clone();
+            std::thread::spawn(move || decoder.decode(&json))
+        };
+        let expected = ("a".to_string(), "b".to_string());
+        assert_eq!(handle.join().unwrap(), Ok(expected.clone()));
+        assert_eq!(decoder.decode(&json), Ok(expected));
+
+        let by_kind = field("kind", string()).and_then(move |kind| match kind.as_str() {
+            "user" => user.clone(),
+            _ => fail::<String>("Unknown kind").shared(),
+        });
+        assert_eq!(
+            by_kind.decode(&serde_json::json!({"kind": "user", "name": "c"})),
+            Ok("c".to_string())
+        );
+        assert_eq!(
+            one_of(vec![by_kind, succeed("default".to_string())])
+                .decode(&serde_json::json!({"kind": "other"})),
+            Ok("default".to_string())
+        );
+    }
+
+    #[test]
+    fn any_decoder_can_be_passed_to_combinators() {
+        let json = serde_json::json!({
+            "data": {"name": "a", "count": null, "sizes": {"1": 2.5}},
+            "shape": {"type": "circle", "radius": 1.5}
+        });
+
+        let decoder =
+            decode_with(|name| move |count| move |sizes| move |shape| (name, count, sizes, shape))
+                .required_at(&["data", "name"], typed::string())
+                .custom(at(
+                    &["data", "count"],
+                    with_default(0, typed::integer::<u8>()),
+                ))
+                .required(
+                    "data",
+                    typed::field(
+                        "sizes",
+                        dict_with_keys::<_, _, Vec<_>>(
+                            typed::integer::<u8>(),
+                            typed::float::<f64>(),
+                        ),
+                    ),
+                )
+                .required(
+                    "shape",
+                    tagged(
+                        "type",
+                        vec![
+                            ("circle", typed::field("radius", typed::float())),
+                            ("square", typed::field("side", typed::float())),
+                        ],
+                    ),
+                )
+                .build();
+        assert_eq!(
+            decoder.decode(&json),
+            Ok(("a".to_string(), 0, vec![(1, 2.5)], 1.5))
+        );
+
+        let name = field_or("name", typed::string(), "none".to_string());
+        assert_eq!(name.decode(&serde_json::json!({})), Ok("none".to_string()));
+        let patch = patch_field("name", typed::string());
+        assert_eq!(patch.decode(&serde_json::json!({})), Ok(Patch::Absent));
+
+        let number = one_of([typed::integer::<i64>(), typed::integer::<i64>()]);
+        assert_eq!(number.decode(&serde_json::json!(1)), Ok(1));
+        let text = typed::string().or(typed::integer::<i64>().map(|i| i.to_string()));
+        assert_eq!(text.decode(&serde_json::json!(1)), Ok("1".to_string()));
+        let tags = lazy(typed::string).list().field("tags");
+        assert_eq!(
+            tags.decode(&serde_json::json!({"tags": ["a"]})),
+            Ok(vec!["a".to_string()])
+        );
+    }
+
+    #[test]
+    fn decode_owned_matches_decode() {
+        // Checks that decode_owned gives the same result as decode, and that it
+        // moves the string at `pointer` rather than cloning it
+        fn assert_moved(
+            decoder: BoxDecoder<'static, String>,
+            json: serde_json::Value,
+            pointer: &str,
+        ) {
+            let string = json.pointer(pointer).and_then(serde_json::Value::as_str);
+            let ptr = string.unwrap().as_ptr();
+            assert_eq!(decoder.decode_owned(json.clone()), decoder.decode(&json));
+            let decoded = decoder.decode_owned(json).unwrap();
+            assert_eq!(decoded.as_ptr(), ptr);
+        }
+
+        let text = serde_json::json!("x".repeat(100));
+        assert_moved(string(), text.clone(), "");
+        assert_moved(field("a", string()), serde_json::json!({ "a": text }), "/a");
+        assert_moved(index(1, string()), serde_json::json!(["b", text]), "/1");
+        assert_moved(
+            map(|mut items: Vec<String>| items.remove(0), list(string())),
+            serde_json::json!([text]),
+            "/0",
+        );
+        assert_moved(
+            map(
+                |mut pairs: Vec<(String, String)>| pairs.remove(0).1,
+                dict(string()),
+            ),
+            serde_json::json!({ "a": text }),
+            "/a",
+        );
+        assert_moved(
+            field_or("a", string(), String::new()),
+            serde_json::json!({ "a": text }),
+            "/a",
+        );
+        assert_moved(with_default(String::new(), string()), text.clone(), "");
+        assert_moved(
+            map(Option::unwrap_or_default, option(string())),
+            text.clone(),
+            "",
+        );
+        assert_moved(
+            tagged("t", vec![("s", field("v", string()))]),
+            serde_json::json!({"t": "s", "v": text}),
+            "/v",
+        );
+        assert_moved(
+            adjacently_tagged("t", "c", vec![("s", string())]),
+            serde_json::json!({"t": "s", "c": text}),
+            "/c",
+        );
+        assert_moved(
+            externally_tagged(vec![("s", string())]),
+            serde_json::json!({ "s": text }),
+            "/s",
+        );
+        assert_moved(lazy(string), text.clone(), "");
+        assert_moved(
+            at(&["a", "b"], string()),
+            serde_json::json!({"a": {"b": text}}),
+            "/a/b",
+        );
+        assert_moved(
+            field("kind", string()).and_then(|kind| field(&kind, string())),
+            serde_json::json!({"kind": "a", "a": text}),
+            "/a",
+        );
+
+        let decoder = field("items", list::<_, Vec<_>>(option(string())));
+        for json in &[
+            serde_json::json!({"items": ["a", null]}),
+            serde_json::json!({"items": ["a", 1]}),
+            serde_json::json!({"other": []}),
+            serde_json::json!([]),
+        ] {
+            assert_eq!(decoder.decode_owned(json.clone()), decoder.decode(json));
+        }
+
+        let decoder = at(&["a", "b"], string());
+        for json in &[
+            serde_json::json!({"a": {"b": 1}}),
+            serde_json::json!({"a": {"c": "x"}}),
+            serde_json::json!({"a": []}),
+            serde_json::json!({}),
+        ] {
+            assert_eq!(decoder.decode_owned(json.clone()), decoder.decode(json));
+        }
+
+        let decoders = vec![
+            adjacently_tagged("t", "c", vec![("s", string())]),
+            externally_tagged(vec![("s", string())]),
+            dict(string()).map(|pairs: Vec<(String, String)>| format!("{:?}", pairs)),
+        ];
+        for decoder in &decoders {
+            for json in &[
+                serde_json::json!({"t": "s", "c": 1}),
+                serde_json::json!({"t": "s"}),
+                serde_json::json!({"t": "x"}),
+                serde_json::json!({"s": 1}),
+                serde_json::json!("s"),
+            ] {
+                assert_eq!(decoder.decode_owned(json.clone()), decoder.decode(json));
+            }
+        }
+    }
 }
@@ -0,0 +1,167 @@
+//! Finds where in the source text a value lives, so that errors from decoding
+//! text can point at a line & column.
+//!
+//! This only runs after a decode has failed, so rather than tracking positions
+//! during parsing it re-scans the text, following the path to the failed value.
+//! The text is assumed to be valid JSON, since it has already been parsed.
+use crate::PathSegment;
+
+/// Returns the line & column (both starting at 1) of the value at `path`.
+///
+/// Columns count bytes rather than characters, as serde_json does for syntax
+/// errors, so that both kinds of error agree.
+pub(crate) fn locate(json: &[u8], path: &[PathSegment]) -> Option<(usize, usize)> {
+    let mut scanner = Scanner { json, pos: 0 };
+    scanner.skip_whitespace();
+    for segment in path {
+        match segment {
+            PathSegment::Field(name) => scanner.enter_field(name)?,
+            PathSegment::Index(index) => scanner.enter_index(*index)?,
+        }
+    }
+    Some(line_and_column(json, scanner.pos))
+}
+
+fn line_and_column(json: &[u8], offset: usize) -> (usize, usize) {
+    let before = &json[..offset];
+    let line_start = before
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let line = before.iter().filter(|b| **b == b'\n').count() + 1;
+    (line, offset - line_start + 1)
+}
+
+struct Scanner<'a> {
+    json: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.json.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        if self.peek()? == byte {
+            self.pos += 1;
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\n') | Some(b'\r') | Some(b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Moves to the start of the value of the named field in the current object.
+    ///
+    /// If the field appears more than once the last one is used, matching
+    /// serde_json.
+    fn enter_field(&mut self, name: &str) -> Option<()> {
+        self.expect(b'{')?;
+        let mut found = None;
+        loop {
+            self.skip_whitespace();
+            if self.peek()? == b'}' {
+                break;
+            }
+            let key = self.read_string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+            if key == name {
+                found = Some(self.pos);
+            }
+            self.skip_value()?;
+            self.skip_whitespace();
+            if self.peek()? == b',' {
+                self.pos += 1;
+            }
+        }
+        self.pos = found?;
+        Some(())
+    }
+
+    /// Moves to the start of the element at `index` in the current array.
+    fn enter_index(&mut self, index: usize) -> Option<()> {
+        self.expect(b'[')?;
+        for _ in 0..index {
+            self.skip_whitespace();
+            self.skip_value()?;
+            self.skip_whitespace();
+            self.expect(b',')?;
+        }
+        self.skip_whitespace();
+        match self.peek()? {
+            b']' => None,
+            _ => Some(()),
+        }
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.skip_string()?;
+        let raw = &self.json[start..self.pos];
+        if raw.contains(&b'\\') {
+            serde_json::from_slice(raw).ok()
+        } else {
+            std::str::from_utf8(&raw[1..raw.len() - 1])
+                .ok()
+                .map(ToString::to_string)
+        }
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        self.expect(b'"')?;
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                b'\\' => self.pos += 2,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => self.skip_string(),
+            b'{' | b'[' => {
+                // Strings are skipped separately, so any brackets we see here are
+                // structural.
+                let mut depth = 0;
+                loop {
+                    match self.peek()? {
+                        b'"' => {
+                            self.skip_string()?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => depth -= 1,
+                        _ => {}
+                    }
+                    self.pos += 1;
+                    if depth == 0 {
+                        return Some(());
+                    }
+                }
+            }
+            _ => {
+                // Numbers, true, false & null
+                while let Some(byte) = self.peek() {
+                    match byte {
+                        b',' | b'}' | b']' | b' ' | b'\n' | b'\r' | b'\t' => break,
+                        _ => self.pos += 1,
+                    }
+                }
+                Some(())
+            }
+        }
+    }
+}
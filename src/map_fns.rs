@@ -1,1427 +1,616 @@
-use crate::{decoders::BoxDecoder, DecodeError, Decoder};
+use crate::{decoders::BoxDecoder, DecodeError, DecodeErrors, Decoder};
+use std::marker::PhantomData;
 
+/// Defines `mapN` & its fallible counterpart `try_mapN`, which share a decoder
+/// struct.  The struct is generic over the types of its decoders, so that it can
+/// also be used by the functions in `typed`.
 macro_rules! define_map_decoder {
-    ($fn_name:ident, $struct_name:ident, $($i:ident),+) => {
+    ($fn_name:ident, $try_fn_name:ident, $struct_name:ident, [$($i:ident),+], [$($d:ident),+]) => {
         #[allow(clippy::too_many_arguments)]
         pub fn $fn_name<'a, F, $($i,)+ NewDecodesTo>(
             func: F,
-            $($i: BoxDecoder<'a, $i>,)+
+            $($i: impl Decoder<'a, $i> + 'a + Send + Sync,)+
         ) -> BoxDecoder<'a, NewDecodesTo>
         where F: Fn($($i, )+) -> NewDecodesTo + 'a + Send + Sync,
             NewDecodesTo: 'a,
             $($i: 'a,)+
         {
             Box::new($struct_name {
-                func: Box::new(func),
-                decoders: (($($i, )+))
+                func: move |$($i, )+| Ok::<_, DecodeError>(func($($i, )+)),
+                decoders: ($($i, )+),
+                phantom: PhantomData,
             })
         }
 
-        struct $struct_name<'a, DecodesTo, $($i,)+> {
-            func: Box<dyn Fn($($i,)+) -> DecodesTo + 'a + Send + Sync>,
-            decoders: ($(BoxDecoder<'a, $i>,)+ )
+        /// Like the `mapN` function of the same arity, but `func` may fail.
+        /// Errors are converted as in `try_map`.
+        #[allow(clippy::too_many_arguments)]
+        pub fn $try_fn_name<'a, F, $($i,)+ NewDecodesTo, E>(
+            func: F,
+            $($i: impl Decoder<'a, $i> + 'a + Send + Sync,)+
+        ) -> BoxDecoder<'a, NewDecodesTo>
+        where F: Fn($($i, )+) -> Result<NewDecodesTo, E> + 'a + Send + Sync,
+            E: Into<DecodeError>,
+            NewDecodesTo: 'a,
+            $($i: 'a,)+
+        {
+            Box::new($struct_name {
+                func,
+                decoders: ($($i, )+),
+                phantom: PhantomData,
+            })
         }
 
-        impl<'a, DecodesTo, $($i,)+> Decoder<'a, DecodesTo>
-        for $struct_name<'a, DecodesTo, $($i,)+> {
+        pub(crate) struct $struct_name<F, $($d,)+ $($i,)+> {
+            pub(crate) func: F,
+            pub(crate) decoders: ($($d,)+),
+            pub(crate) phantom: PhantomData<fn() -> ($($i,)+)>,
+        }
+
+        impl<'a, DecodesTo, E, F, $($d,)+ $($i,)+> Decoder<'a, DecodesTo>
+        for $struct_name<F, $($d,)+ $($i,)+>
+        where F: Fn($($i, )+) -> Result<DecodesTo, E>,
+            E: Into<DecodeError>,
+            $($d: Decoder<'a, $i>,)+
+        {
             fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
                 let ($($i, )+) = &self.decoders;
                 $(
-                    let $i = (*$i).decode(value)?;
+                    let $i = $i.decode(value)?;
+                )+
+                (self.func)($($i, )+).map_err(Into::into)
+            }
+
+            fn decode_all_errors(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeErrors> {
+                let ($($i, )+) = &self.decoders;
+                let mut errors = Vec::new();
+                $(
+                    let $i = $i
+                        .decode_all_errors(value)
+                        .map_err(|e| errors.extend(e))
+                        .ok();
                 )+
-                let result = (*self.func)($($i, )+);
-                Ok(result)
+                match ($($i, )+) {
+                    ($(Some($i), )+) => Ok((self.func)($($i, )+).map_err(Into::into)?),
+                    _ => Err(DecodeErrors::new(errors)),
+                }
             }
         }
     }
 }
 
-define_map_decoder!(map2, Fn2Decoder, _1, _2);
-define_map_decoder!(map3, Fn3Decoder, _1, _2, _3);
-define_map_decoder!(map4, Fn4Decoder, _1, _2, _3, _4);
-define_map_decoder!(map5, Fn5Decoder, _1, _2, _3, _4, _5);
-define_map_decoder!(map6, Fn6Decoder, _1, _2, _3, _4, _5, _6);
-define_map_decoder!(map7, Fn7Decoder, _1, _2, _3, _4, _5, _6, _7);
-define_map_decoder!(map8, Fn8Decoder, _1, _2, _3, _4, _5, _6, _7, _8);
-define_map_decoder!(map9, Fn9Decoder, _1, _2, _3, _4, _5, _6, _7, _8, _9);
-define_map_decoder!(map10, Fn10Decoder, _1, _2, _3, _4, _5, _6, _7, _8, _9, _10);
+define_map_decoder!(map2, try_map2, Fn2Decoder, [_1, _2], [D1, D2]);
+define_map_decoder!(map3, try_map3, Fn3Decoder, [_1, _2, _3], [D1, D2, D3]);
+define_map_decoder!(
+    map4,
+    try_map4,
+    Fn4Decoder,
+    [_1, _2, _3, _4],
+    [D1, D2, D3, D4]
+);
+define_map_decoder!(
+    map5,
+    try_map5,
+    Fn5Decoder,
+    [_1, _2, _3, _4, _5],
+    [D1, D2, D3, D4, D5]
+);
+define_map_decoder!(
+    map6,
+    try_map6,
+    Fn6Decoder,
+    [_1, _2, _3, _4, _5, _6],
+    [D1, D2, D3, D4, D5, D6]
+);
+define_map_decoder!(
+    map7,
+    try_map7,
+    Fn7Decoder,
+    [_1, _2, _3, _4, _5, _6, _7],
+    [D1, D2, D3, D4, D5, D6, D7]
+);
+define_map_decoder!(
+    map8,
+    try_map8,
+    Fn8Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8],
+    [D1, D2, D3, D4, D5, D6, D7, D8]
+);
+define_map_decoder!(
+    map9,
+    try_map9,
+    Fn9Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9]
+);
+define_map_decoder!(
+    map10,
+    try_map10,
+    Fn10Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10]
+);
 define_map_decoder!(
     map11,
+    try_map11,
     Fn11Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11]
 );
 define_map_decoder!(
     map12,
+    try_map12,
     Fn12Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12]
 );
 define_map_decoder!(
     map13,
+    try_map13,
     Fn13Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13]
 );
 define_map_decoder!(
     map14,
+    try_map14,
     Fn14Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14]
 );
 define_map_decoder!(
     map15,
+    try_map15,
     Fn15Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15]
 );
 define_map_decoder!(
     map16,
+    try_map16,
     Fn16Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16]
 );
 define_map_decoder!(
     map17,
+    try_map17,
     Fn17Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17]
 );
 define_map_decoder!(
     map18,
+    try_map18,
     Fn18Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18]
 );
 define_map_decoder!(
     map19,
+    try_map19,
     Fn19Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19]
 );
 define_map_decoder!(
     map20,
+    try_map20,
     Fn20Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20]
 );
 define_map_decoder!(
     map21,
+    try_map21,
     Fn21Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21
+    ]
 );
 define_map_decoder!(
     map22,
+    try_map22,
     Fn22Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22
+    ]
 );
 define_map_decoder!(
     map23,
+    try_map23,
     Fn23Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23
+    ]
 );
 define_map_decoder!(
     map24,
+    try_map24,
     Fn24Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24
+    ]
 );
 define_map_decoder!(
     map25,
+    try_map25,
     Fn25Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25
+    ]
 );
 define_map_decoder!(
     map26,
+    try_map26,
     Fn26Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26
+    ]
 );
 define_map_decoder!(
     map27,
+    try_map27,
     Fn27Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27
+    ]
 );
 define_map_decoder!(
     map28,
+    try_map28,
     Fn28Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28
+    ]
 );
 define_map_decoder!(
     map29,
+    try_map29,
     Fn29Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29
+    ]
 );
 define_map_decoder!(
     map30,
+    try_map30,
     Fn30Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30
+    ]
 );
 define_map_decoder!(
     map31,
+    try_map31,
     Fn31Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31
+    ]
 );
 define_map_decoder!(
     map32,
+    try_map32,
     Fn32Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32
+    ]
 );
 define_map_decoder!(
     map33,
+    try_map33,
     Fn33Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33
+    ]
 );
 define_map_decoder!(
     map34,
+    try_map34,
     Fn34Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34
+    ]
 );
 define_map_decoder!(
     map35,
+    try_map35,
     Fn35Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35
+    ]
 );
 define_map_decoder!(
     map36,
+    try_map36,
     Fn36Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36
+    ]
 );
 define_map_decoder!(
     map37,
+    try_map37,
     Fn37Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37
+    ]
 );
 define_map_decoder!(
     map38,
+    try_map38,
     Fn38Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38
+    ]
 );
 define_map_decoder!(
     map39,
+    try_map39,
     Fn39Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39
+    ]
 );
 define_map_decoder!(
     map40,
+    try_map40,
     Fn40Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40
+    ]
 );
 define_map_decoder!(
     map41,
+    try_map41,
     Fn41Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41
+    ]
 );
 define_map_decoder!(
     map42,
+    try_map42,
     Fn42Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42
+    ]
 );
 define_map_decoder!(
     map43,
+    try_map43,
     Fn43Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42,
-    _43
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43
+    ]
 );
 define_map_decoder!(
     map44,
+    try_map44,
     Fn44Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42,
-    _43,
-    _44
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44
+    ]
 );
 define_map_decoder!(
     map45,
+    try_map45,
     Fn45Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42,
-    _43,
-    _44,
-    _45
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45
+    ]
 );
 define_map_decoder!(
     map46,
+    try_map46,
     Fn46Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42,
-    _43,
-    _44,
-    _45,
-    _46
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46
+    ]
 );
 define_map_decoder!(
     map47,
+    try_map47,
     Fn47Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42,
-    _43,
-    _44,
-    _45,
-    _46,
-    _47
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46, _47
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46, D47
+    ]
 );
 define_map_decoder!(
     map48,
+    try_map48,
     Fn48Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42,
-    _43,
-    _44,
-    _45,
-    _46,
-    _47,
-    _48
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46, _47, _48
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46, D47, D48
+    ]
 );
 define_map_decoder!(
     map49,
+    try_map49,
     Fn49Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42,
-    _43,
-    _44,
-    _45,
-    _46,
-    _47,
-    _48,
-    _49
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46, _47, _48, _49
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46, D47, D48, D49
+    ]
 );
 define_map_decoder!(
     map50,
+    try_map50,
     Fn50Decoder,
-    _1,
-    _2,
-    _3,
-    _4,
-    _5,
-    _6,
-    _7,
-    _8,
-    _9,
-    _10,
-    _11,
-    _12,
-    _13,
-    _14,
-    _15,
-    _16,
-    _17,
-    _18,
-    _19,
-    _20,
-    _21,
-    _22,
-    _23,
-    _24,
-    _25,
-    _26,
-    _27,
-    _28,
-    _29,
-    _30,
-    _31,
-    _32,
-    _33,
-    _34,
-    _35,
-    _36,
-    _37,
-    _38,
-    _39,
-    _40,
-    _41,
-    _42,
-    _43,
-    _44,
-    _45,
-    _46,
-    _47,
-    _48,
-    _49,
-    _50
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46, _47, _48, _49, _50
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46, D47, D48, D49, D50
+    ]
 );
@@ -0,0 +1,122 @@
+use crate::{
+    decoders::{at, field, field_or, succeed},
+    map2, BoxDecoder, Decoder,
+};
+
+/// Starts building a decoder in the style of Elm's `Json.Decode.Pipeline`.
+///
+/// `func` should be a curried function that takes one argument at a time, with
+/// each step of the pipeline providing the next argument:
+///
+/// ```
+/// use json_decode::{decode_with, integer, string, DecoderExt};
+///
+/// struct User {
+///     id: i64,
+///     nick: Option<String>,
+///     score: u32,
+/// }
+///
+/// let decoder = decode_with(|id| move |nick| move |score| User { id, nick, score })
+///     .required("id", integer())
+///     .optional("nick", string().optional(), None)
+///     .hardcoded(0)
+///     .build();
+/// ```
+///
+/// Unlike the `mapN` functions there is no limit on the number of arguments.
+pub fn decode_with<'a, F>(func: F) -> Pipeline<'a, F>
+where
+    F: Clone + Send + Sync + 'a,
+{
+    Pipeline {
+        decoder: succeed(func),
+    }
+}
+
+/// A partially built decoder.  See `decode_with`.
+pub struct Pipeline<'a, F> {
+    decoder: BoxDecoder<'a, F>,
+}
+
+impl<'a, F> Pipeline<'a, F>
+where
+    F: 'a,
+{
+    /// Provides the next argument by decoding the named field with `decoder`.
+    pub fn required<A, G>(
+        self,
+        field_name: &str,
+        decoder: impl Decoder<'a, A> + 'a + Send + Sync,
+    ) -> Pipeline<'a, G>
+    where
+        F: FnOnce(A) -> G,
+        A: 'a,
+        G: 'a,
+    {
+        self.custom(field(field_name, decoder))
+    }
+
+    /// Provides the next argument by decoding a nested field with `decoder`.  See `at`.
+    pub fn required_at<A, G>(
+        self,
+        path: &[&str],
+        decoder: impl Decoder<'a, A> + 'a + Send + Sync,
+    ) -> Pipeline<'a, G>
+    where
+        F: FnOnce(A) -> G,
+        A: 'a,
+        G: 'a,
+    {
+        self.custom(at(path, decoder))
+    }
+
+    /// Provides the next argument by decoding the named field with `decoder`,
+    /// or `default` if the field is missing or `null`.  See `field_or`.
+    pub fn optional<A, G>(
+        self,
+        field_name: &str,
+        decoder: impl Decoder<'a, A> + 'a + Send + Sync,
+        default: A,
+    ) -> Pipeline<'a, G>
+    where
+        F: FnOnce(A) -> G,
+        A: Clone + Send + Sync + 'a,
+        G: 'a,
+    {
+        self.custom(field_or(field_name, decoder, default))
+    }
+
+    /// Provides the next argument without looking at the JSON.
+    pub fn hardcoded<A, G>(self, value: A) -> Pipeline<'a, G>
+    where
+        F: FnOnce(A) -> G,
+        A: Clone + Send + Sync + 'a,
+        G: 'a,
+    {
+        self.custom(succeed(value))
+    }
+
+    /// Provides the next argument by running `decoder` against the whole value.
+    pub fn custom<A, G>(self, decoder: impl Decoder<'a, A> + 'a + Send + Sync) -> Pipeline<'a, G>
+    where
+        F: FnOnce(A) -> G,
+        A: 'a,
+        G: 'a,
+    {
+        Pipeline {
+            decoder: map2(|func: F, arg| func(arg), self.decoder, decoder),
+        }
+    }
+
+    /// Finishes the pipeline, returning the decoder it describes.
+    pub fn build(self) -> BoxDecoder<'a, F> {
+        self.decoder
+    }
+}
+
+impl<'a, T> From<Pipeline<'a, T>> for BoxDecoder<'a, T> {
+    fn from(pipeline: Pipeline<'a, T>) -> Self {
+        pipeline.decoder
+    }
+}
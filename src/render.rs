@@ -0,0 +1,104 @@
+//! Renders errors as multi-line messages for humans, in the style of Elm's
+//! `Json.Decode.errorToString`.
+use crate::{DecodeError, PathSegment};
+
+/// Renders `error` as a multi-line, human readable message.
+///
+/// Unlike the `Display` implementation of `DecodeError` this includes a pretty
+/// printed preview of the value that failed to decode, and lists every
+/// alternative that was tried by a failed `one_of`.  For example:
+///
+/// ```text
+/// Problem with the value at json.items[1].price:
+///
+///     "2"
+///
+/// Expected a Number
+/// ```
+pub fn error_to_string(error: &DecodeError) -> String {
+    render(error, &[])
+}
+
+/// Renders `error`, which occurred within the value at `context`.
+fn render(error: &DecodeError, context: &[PathSegment]) -> String {
+    let mut path = context.to_vec();
+    path.extend(error.path());
+    let subject = if path.is_empty() {
+        "the given value".to_string()
+    } else {
+        let path: String = path.iter().map(ToString::to_string).collect();
+        format!("the value at json{}", path)
+    };
+
+    match error.root_cause() {
+        DecodeError::OneOf(alternatives) => {
+            let mut output = format!(
+                "None of the alternatives for {} matched, they failed in the following {} ways:",
+                subject,
+                alternatives.len()
+            );
+            for (i, alternative) in alternatives.iter().enumerate() {
+                let rendered = format!("({}) {}", i + 1, render(alternative, &path));
+                output.push_str("\n\n");
+                output.push_str(&indent_after_first_line(&rendered));
+            }
+            output
+        }
+        root @ DecodeError::InvalidJson { .. } | root @ DecodeError::Io(_) => root.to_string(),
+        root => {
+            let mut output = format!("Problem with {}", subject);
+            if let Some((line, column)) = error.location() {
+                output.push_str(&format!(" (line {}, column {})", line, column));
+            }
+            output.push(':');
+            if let Some(snippet) = root.snippet() {
+                let pretty = snippet.to_pretty_string();
+                output.push_str("\n\n");
+                for line in pretty.lines() {
+                    output.push_str("    ");
+                    output.push_str(line);
+                    output.push('\n');
+                }
+            } else {
+                output.push('\n');
+            }
+            output.push('\n');
+            output.push_str(&problem(root));
+            output
+        }
+    }
+}
+
+/// Describes the problem with a value, without repeating the value itself.
+fn problem(root: &DecodeError) -> String {
+    match root {
+        DecodeError::IncorrectType(expected, _) => {
+            let name = expected.to_string();
+            let article = match name.chars().next() {
+                Some('A') | Some('E') | Some('I') | Some('O') | Some('U') => "an",
+                _ => "a",
+            };
+            format!("Expected {} {}", article, name)
+        }
+        DecodeError::MissingField(name, _) => {
+            format!("Expected an Object with a field named `{}`", name)
+        }
+        DecodeError::InvalidInteger(_) => "Expected an integer".to_string(),
+        DecodeError::NumberOutOfRange(_) => "Expected a number within range".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn indent_after_first_line(text: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("    {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
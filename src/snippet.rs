@@ -0,0 +1,296 @@
+use crate::JsonType;
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    static SNIPPET_LIMIT: Cell<usize> = const { Cell::new(100) };
+}
+
+/// Runs `func` with errors keeping roughly `limit` characters of each JSON
+/// value for their messages, rather than the default of 100.  Returns the
+/// result of `func`.
+///
+/// The limit only applies to errors created on the current thread while `func`
+/// is running, so other decodes are unaffected, e.g.
+/// `with_snippet_limit(20, || decoder.decode_str(json))`.
+pub fn with_snippet_limit<T>(limit: usize, func: impl FnOnce() -> T) -> T {
+    struct Restore(usize);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SNIPPET_LIMIT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(SNIPPET_LIMIT.with(|current| current.replace(limit)));
+    func()
+}
+
+/// A bounded preview of the JSON value that caused an error.
+///
+/// Errors keep one of these rather than the whole value, so that failing to
+/// decode a huge document doesn't produce a huge error.  Long strings & object
+/// keys are cut short and end in `…`, while arrays & objects have their later
+/// entries left out.  `is_truncated` says whether anything was cut.  Nothing is
+/// rendered until the snippet is displayed, at which point arrays & objects
+/// that had entries left out end in `…`, e.g. `{"a":1,…}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snippet {
+    preview: serde_json::Value,
+    truncated: bool,
+    /// JSON pointers to the arrays & objects in `preview` that had entries left
+    /// out.
+    cut: Vec<String>,
+}
+
+impl Snippet {
+    /// Creates a snippet of `value`, using the limit set by `with_snippet_limit`.
+    pub fn new(value: &serde_json::Value) -> Self {
+        Snippet::with_limit(value, current_limit())
+    }
+
+    /// Creates a snippet of `value` that keeps roughly `limit` characters.
+    pub fn with_limit(value: &serde_json::Value, limit: usize) -> Self {
+        let mut truncator = Truncator {
+            budget: limit,
+            truncated: false,
+            cut: Vec::new(),
+        };
+        let preview = truncator.value(value, "");
+        Snippet {
+            preview,
+            truncated: truncator.truncated,
+            cut: truncator.cut,
+        }
+    }
+
+    /// Creates a snippet of the string `s`, without copying any more of it than
+    /// the limit set by `with_snippet_limit` allows.
+    pub(crate) fn of_str(s: &str) -> Self {
+        let mut truncator = Truncator {
+            budget: current_limit(),
+            truncated: false,
+            cut: Vec::new(),
+        };
+        truncator.charge(2);
+        let preview = serde_json::Value::String(truncator.string(s));
+        Snippet {
+            preview,
+            truncated: truncator.truncated,
+            cut: truncator.cut,
+        }
+    }
+
+    /// The (possibly truncated) value.  This only contains entries from the
+    /// original value, though long strings & keys may have been cut short.
+    pub fn preview(&self) -> &serde_json::Value {
+        &self.preview
+    }
+
+    /// The kind of the original value
+    pub fn kind(&self) -> JsonType {
+        // Truncation never changes the kind of the top level value
+        JsonType::of(&self.preview)
+    }
+
+    /// Whether any of the original value was left out of the preview
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Renders the snippet over several indented lines, like
+    /// `serde_json::to_string_pretty`.
+    pub(crate) fn to_pretty_string(&self) -> String {
+        let mut output = String::new();
+        self.render(&self.preview, "", Some(0), &mut output);
+        output
+    }
+
+    /// Renders `value`, found at `pointer` in the preview, onto `output`.
+    /// `indent` is the current indentation when pretty printing.
+    fn render(
+        &self,
+        value: &serde_json::Value,
+        pointer: &str,
+        indent: Option<usize>,
+        output: &mut String,
+    ) {
+        use serde_json::Value;
+
+        let (open, close, entries): (char, char, Vec<(Option<&String>, &Value)>) = match value {
+            Value::Array(items) => ('[', ']', items.iter().map(|item| (None, item)).collect()),
+            Value::Object(map) => (
+                '{',
+                '}',
+                map.iter().map(|(key, item)| (Some(key), item)).collect(),
+            ),
+            scalar => {
+                output.push_str(&scalar.to_string());
+                return;
+            }
+        };
+        let is_cut = self.cut.iter().any(|cut| cut == pointer);
+
+        output.push(open);
+        let inner_indent = indent.map(|indent| indent + 2);
+        let mut first = true;
+        let mut separate = |output: &mut String| {
+            if !first {
+                output.push(',');
+            }
+            first = false;
+            if let Some(indent) = inner_indent {
+                output.push('\n');
+                output.push_str(&" ".repeat(indent));
+            }
+        };
+        for (i, (key, item)) in entries.iter().enumerate() {
+            separate(output);
+            let segment = match key {
+                Some(key) => {
+                    output.push_str(&Value::String(key.to_string()).to_string());
+                    output.push(':');
+                    if indent.is_some() {
+                        output.push(' ');
+                    }
+                    key.to_string()
+                }
+                None => i.to_string(),
+            };
+            self.render(
+                item,
+                &child_pointer(pointer, &segment),
+                inner_indent,
+                output,
+            );
+        }
+        if is_cut {
+            separate(output);
+            output.push_str(ELLIPSIS);
+        }
+        if let (Some(indent), false) = (indent, entries.is_empty() && !is_cut) {
+            output.push('\n');
+            output.push_str(&" ".repeat(indent));
+        }
+        output.push(close);
+    }
+}
+
+fn current_limit() -> usize {
+    SNIPPET_LIMIT.with(Cell::get)
+}
+
+/// Returns the pointer to the entry named `segment` within the value at
+/// `pointer`.
+fn child_pointer(pointer: &str, segment: &str) -> String {
+    format!(
+        "{}/{}",
+        pointer,
+        segment.replace('~', "~0").replace('/', "~1")
+    )
+}
+
+const ELLIPSIS: &str = "…";
+
+/// Copies as much of a value as the budget allows.
+struct Truncator {
+    budget: usize,
+    truncated: bool,
+    cut: Vec<String>,
+}
+
+impl Truncator {
+    /// Takes `cost` from the budget, saying whether there was any budget left
+    /// to take it from.
+    fn charge(&mut self, cost: usize) -> bool {
+        let had_budget = self.budget > 0;
+        self.budget = self.budget.saturating_sub(cost);
+        had_budget
+    }
+
+    /// Copies as much of `s` as the budget allows, ending it in `…` if it had
+    /// to be cut short.
+    fn string(&mut self, s: &str) -> String {
+        // Only count as far as we need to, so huge strings stay cheap
+        let len = s.chars().take(self.budget + 1).count();
+        if len > self.budget {
+            self.truncated = true;
+            let mut cut: String = s.chars().take(self.budget).collect();
+            cut.push_str(ELLIPSIS);
+            self.budget = 0;
+            cut
+        } else {
+            self.budget -= len;
+            s.to_string()
+        }
+    }
+
+    /// Copies as much of `value`, which is at `pointer` in the preview, as the
+    /// budget allows.
+    fn value(&mut self, value: &serde_json::Value, pointer: &str) -> serde_json::Value {
+        use serde_json::Value;
+
+        // Every value is charged roughly what it costs to render, including
+        // quotes, brackets & separators, so that even empty strings & containers
+        // use up the budget.
+        match value {
+            Value::String(s) => {
+                self.charge(2);
+                Value::String(self.string(s))
+            }
+            Value::Array(items) => {
+                self.charge(2);
+                let mut preview = Vec::new();
+                for (i, item) in items.iter().enumerate() {
+                    if !self.charge(1) {
+                        self.cut(pointer);
+                        break;
+                    }
+                    let item = self.value(item, &child_pointer(pointer, &i.to_string()));
+                    preview.push(item);
+                }
+                Value::Array(preview)
+            }
+            Value::Object(map) => {
+                self.charge(2);
+                let mut preview = serde_json::Map::new();
+                for (key, item) in map {
+                    // The key's quotes, the colon & the separator
+                    if !self.charge(4) {
+                        self.cut(pointer);
+                        break;
+                    }
+                    let key = self.string(key);
+                    let item = self.value(item, &child_pointer(pointer, &key));
+                    preview.insert(key, item);
+                }
+                Value::Object(preview)
+            }
+            Value::Number(_) | Value::Bool(_) | Value::Null => {
+                // Rough size of the rendered scalar, without rendering it
+                self.charge(4);
+                value.clone()
+            }
+        }
+    }
+
+    /// Records that the container at `pointer` had entries left out.
+    fn cut(&mut self, pointer: &str) {
+        self.truncated = true;
+        self.cut.push(pointer.to_string());
+    }
+}
+
+impl fmt::Display for Snippet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut output = String::new();
+        self.render(&self.preview, "", None, &mut output);
+        f.write_str(&output)
+    }
+}
+
+impl From<&serde_json::Value> for Snippet {
+    fn from(value: &serde_json::Value) -> Self {
+        Snippet::new(value)
+    }
+}
@@ -0,0 +1,903 @@
+//! Decoder functions that avoid boxing.
+//!
+//! The functions at the top level of this crate return `BoxDecoder`s, which are
+//! easy to store & pass around but cost an allocation per decoder and a virtual
+//! call per decode.  The functions in this module mirror them, but return
+//! concrete decoder types & accept any `Decoder`, so the compiler can inline a
+//! whole tree of decoders.  Use `Decoder::boxed` to turn one of these into a
+//! `BoxDecoder` when you need to name its type:
+//!
+//! ```
+//! use json_decode::{typed, BoxDecoder, Decoder};
+//!
+//! fn point_decoder() -> BoxDecoder<'static, (f64, f64)> {
+//!     typed::map2(
+//!         |x, y| (x, y),
+//!         typed::field("x", typed::float()),
+//!         typed::field("y", typed::float()),
+//!     )
+//!     .boxed()
+//! }
+//!
+//! let point = point_decoder().decode(&serde_json::json!({"x": 1.0, "y": 2.0}));
+//! assert_eq!(point.unwrap(), (1.0, 2.0));
+//! ```
+//!
+//! `BoxDecoder`s also implement `Decoder`, so the two styles can be mixed.
+//! Decoders without an equivalent here (e.g. `dict` or `tagged`) can be used
+//! through their boxed versions.
+use crate::{
+    decoders::{
+        AndThenWithDecoder, AtDecoder, BooleanDecoder, DecoderAndThen, DecoderFn1,
+        ExactLengthDecoder, FailDecoder, FailWithDecoder, FieldDecoder, FieldOrDecoder,
+        FloatDecoder, IndexDecoder, IntDecoder, JsonDecoder, LazyDecoder, ListDecoder,
+        MapErrDecoder, NullDecoder, OneOfDecoder, OptionDecoder, OptionalFieldDecoder,
+        SerdeDecoder, StringDecoder, SucceedDecoder, SucceedWithDecoder, TryMapDecoder,
+        UIntDecoder, WithDefaultDecoder,
+    },
+    DecodeError, Decoder,
+};
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+/// See `json_decode::string`
+pub fn string<'a>() -> impl Decoder<'a, String> {
+    StringDecoder {}
+}
+
+/// See `json_decode::integer`
+pub fn integer<'a, I>() -> impl Decoder<'a, I>
+where
+    I: TryFrom<i64>,
+{
+    IntDecoder {
+        phantom: PhantomData,
+    }
+}
+
+/// See `json_decode::unsigned_integer`
+pub fn unsigned_integer<'a, I>() -> impl Decoder<'a, I>
+where
+    I: TryFrom<u64>,
+{
+    UIntDecoder {
+        phantom: PhantomData,
+    }
+}
+
+/// See `json_decode::float`
+pub fn float<'a, F>() -> impl Decoder<'a, F>
+where
+    F: From<f64>,
+{
+    FloatDecoder {
+        phantom: PhantomData,
+    }
+}
+
+/// See `json_decode::boolean`
+pub fn boolean<'a>() -> impl Decoder<'a, bool> {
+    BooleanDecoder {}
+}
+
+/// See `json_decode::null`
+pub fn null<'a, V>(value: V) -> impl Decoder<'a, V>
+where
+    V: Clone + Send + Sync + 'a,
+{
+    NullDecoder { value }
+}
+
+/// See `json_decode::json`
+pub fn json<'a>() -> impl Decoder<'a, serde_json::Value> {
+    JsonDecoder {}
+}
+
+/// See `json_decode::serde`
+pub fn serde<'a, T>() -> impl Decoder<'a, T>
+where
+    for<'de> T: serde::Deserialize<'de>,
+{
+    SerdeDecoder {
+        phantom: PhantomData,
+    }
+}
+
+/// See `json_decode::succeed`
+pub fn succeed<'a, V>(value: V) -> impl Decoder<'a, V>
+where
+    V: Clone + Send + Sync + 'a,
+{
+    SucceedDecoder { value }
+}
+
+/// See `json_decode::succeed_with`
+pub fn succeed_with<'a, F, V>(func: F) -> impl Decoder<'a, V>
+where
+    F: Fn() -> V,
+{
+    SucceedWithDecoder { func }
+}
+
+/// See `json_decode::fail`
+pub fn fail<'a, V>(error: impl Into<String>) -> impl Decoder<'a, V> {
+    FailDecoder {
+        error: error.into(),
+    }
+}
+
+/// See `json_decode::fail_with`
+pub fn fail_with<'a, V>(error: impl Into<DecodeError>) -> impl Decoder<'a, V> {
+    FailWithDecoder {
+        error: error.into(),
+    }
+}
+
+/// See `json_decode::field`
+pub fn field<'a, T, D>(field_name: &str, decoder: D) -> impl Decoder<'a, T>
+where
+    D: Decoder<'a, T>,
+{
+    FieldDecoder {
+        field_name: field_name.to_string(),
+        inner_decoder: decoder,
+    }
+}
+
+/// See `json_decode::optional_field`
+pub fn optional_field<'a, T, D>(field_name: &str, decoder: D) -> impl Decoder<'a, Option<T>>
+where
+    D: Decoder<'a, T>,
+{
+    OptionalFieldDecoder {
+        field_name: field_name.to_string(),
+        inner_decoder: decoder,
+    }
+}
+
+/// See `json_decode::field_or`
+pub fn field_or<'a, T, D>(field_name: &str, decoder: D, default: T) -> impl Decoder<'a, T>
+where
+    D: Decoder<'a, T>,
+    T: Clone,
+{
+    FieldOrDecoder {
+        field_name: field_name.to_string(),
+        inner_decoder: decoder,
+        default: move || default.clone(),
+    }
+}
+
+/// See `json_decode::field_or_default`
+pub fn field_or_default<'a, T, D>(field_name: &str, decoder: D) -> impl Decoder<'a, T>
+where
+    D: Decoder<'a, T>,
+    T: Default,
+{
+    FieldOrDecoder {
+        field_name: field_name.to_string(),
+        inner_decoder: decoder,
+        default: T::default,
+    }
+}
+
+/// See `json_decode::with_default`
+pub fn with_default<'a, T, D>(default: T, decoder: D) -> impl Decoder<'a, T>
+where
+    D: Decoder<'a, T>,
+    T: Clone,
+{
+    WithDefaultDecoder {
+        inner_decoder: decoder,
+        default: move || default.clone(),
+    }
+}
+
+/// See `json_decode::at`
+pub fn at<'a, T, D>(path: &[&str], decoder: D) -> impl Decoder<'a, T>
+where
+    D: Decoder<'a, T>,
+{
+    AtDecoder {
+        path: path.iter().map(ToString::to_string).collect(),
+        inner_decoder: decoder,
+    }
+}
+
+/// See `json_decode::index`
+pub fn index<'a, T, D>(index: usize, decoder: D) -> impl Decoder<'a, T>
+where
+    D: Decoder<'a, T>,
+{
+    IndexDecoder {
+        index,
+        inner_decoder: decoder,
+    }
+}
+
+/// See `json_decode::exact_length`
+pub fn exact_length<'a, T, D>(length: usize, decoder: D) -> impl Decoder<'a, T>
+where
+    D: Decoder<'a, T>,
+{
+    ExactLengthDecoder { length, decoder }
+}
+
+/// See `json_decode::option`
+pub fn option<'a, T, D>(decoder: D) -> impl Decoder<'a, Option<T>>
+where
+    D: Decoder<'a, T>,
+{
+    OptionDecoder {
+        inner_decoder: decoder,
+    }
+}
+
+/// See `json_decode::list`
+pub fn list<'a, Item, Collection, D>(decoder: D) -> impl Decoder<'a, Collection>
+where
+    D: Decoder<'a, Item>,
+    Collection: FromIterator<Item>,
+{
+    ListDecoder {
+        inner_decoder: decoder,
+        phantom: PhantomData,
+    }
+}
+
+/// See `json_decode::one_of`
+pub fn one_of<'a, T, D>(decoders: impl IntoIterator<Item = D>) -> impl Decoder<'a, T>
+where
+    D: Decoder<'a, T>,
+{
+    OneOfDecoder {
+        decoders: decoders.into_iter().collect(),
+    }
+}
+
+/// See `json_decode::lazy`
+pub fn lazy<'a, F, T, D>(func: F) -> impl Decoder<'a, T>
+where
+    F: Fn() -> D,
+    D: Decoder<'a, T>,
+{
+    LazyDecoder { func }
+}
+
+/// See `json_decode::map`
+pub fn map<'a, F, T1, NewDecodesTo, D>(func: F, d1: D) -> impl Decoder<'a, NewDecodesTo>
+where
+    F: Fn(T1) -> NewDecodesTo,
+    D: Decoder<'a, T1>,
+{
+    DecoderFn1 {
+        func,
+        decoder: d1,
+        phantom: PhantomData,
+    }
+}
+
+/// See `json_decode::try_map`
+pub fn try_map<'a, F, T1, NewDecodesTo, E, D>(func: F, d1: D) -> impl Decoder<'a, NewDecodesTo>
+where
+    F: Fn(T1) -> Result<NewDecodesTo, E>,
+    E: Into<DecodeError>,
+    D: Decoder<'a, T1>,
+{
+    TryMapDecoder {
+        func,
+        decoder: d1,
+        phantom: PhantomData,
+    }
+}
+
+/// See `json_decode::map_err`
+pub fn map_err<'a, F, T, D>(func: F, decoder: D) -> impl Decoder<'a, T>
+where
+    F: Fn(DecodeError) -> DecodeError,
+    D: Decoder<'a, T>,
+{
+    MapErrDecoder { func, decoder }
+}
+
+/// See `json_decode::and_then`
+pub fn and_then<'a, F, T, NewDecodesTo, D, Inner>(func: F, d: D) -> impl Decoder<'a, NewDecodesTo>
+where
+    F: Fn(T) -> Inner,
+    D: Decoder<'a, T>,
+    Inner: Decoder<'a, NewDecodesTo>,
+{
+    DecoderAndThen {
+        func,
+        decoder: d,
+        phantom: PhantomData,
+    }
+}
+
+/// See `json_decode::and_then_with`
+pub fn and_then_with<'a, F, T, NewDecodesTo, E, D>(func: F, d: D) -> impl Decoder<'a, NewDecodesTo>
+where
+    F: Fn(T, &serde_json::Value) -> Result<NewDecodesTo, E>,
+    E: Into<DecodeError>,
+    D: Decoder<'a, T>,
+{
+    AndThenWithDecoder {
+        func,
+        decoder: d,
+        phantom: PhantomData,
+    }
+}
+
+macro_rules! define_typed_map {
+    ($fn_name:ident, $try_fn_name:ident, $struct_name:ident, [$($i:ident),+], [$($d:ident),+]) => {
+        /// See the boxed function of the same name
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fn_name<'a, F, $($i,)+ $($d,)+ NewDecodesTo>(
+            func: F,
+            $($i: $d,)+
+        ) -> impl Decoder<'a, NewDecodesTo>
+        where F: Fn($($i, )+) -> NewDecodesTo,
+            $($d: Decoder<'a, $i>,)+
+        {
+            crate::map_fns::$struct_name {
+                func: move |$($i, )+| Ok::<_, DecodeError>(func($($i, )+)),
+                decoders: ($($i, )+),
+                phantom: PhantomData,
+            }
+        }
+
+        /// See the boxed function of the same name
+        #[allow(clippy::too_many_arguments)]
+        pub fn $try_fn_name<'a, F, $($i,)+ $($d,)+ NewDecodesTo, E>(
+            func: F,
+            $($i: $d,)+
+        ) -> impl Decoder<'a, NewDecodesTo>
+        where F: Fn($($i, )+) -> Result<NewDecodesTo, E>,
+            E: Into<DecodeError>,
+            $($d: Decoder<'a, $i>,)+
+        {
+            crate::map_fns::$struct_name {
+                func,
+                decoders: ($($i, )+),
+                phantom: PhantomData,
+            }
+        }
+    }
+}
+
+define_typed_map!(map2, try_map2, Fn2Decoder, [_1, _2], [D1, D2]);
+define_typed_map!(map3, try_map3, Fn3Decoder, [_1, _2, _3], [D1, D2, D3]);
+define_typed_map!(
+    map4,
+    try_map4,
+    Fn4Decoder,
+    [_1, _2, _3, _4],
+    [D1, D2, D3, D4]
+);
+define_typed_map!(
+    map5,
+    try_map5,
+    Fn5Decoder,
+    [_1, _2, _3, _4, _5],
+    [D1, D2, D3, D4, D5]
+);
+define_typed_map!(
+    map6,
+    try_map6,
+    Fn6Decoder,
+    [_1, _2, _3, _4, _5, _6],
+    [D1, D2, D3, D4, D5, D6]
+);
+define_typed_map!(
+    map7,
+    try_map7,
+    Fn7Decoder,
+    [_1, _2, _3, _4, _5, _6, _7],
+    [D1, D2, D3, D4, D5, D6, D7]
+);
+define_typed_map!(
+    map8,
+    try_map8,
+    Fn8Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8],
+    [D1, D2, D3, D4, D5, D6, D7, D8]
+);
+define_typed_map!(
+    map9,
+    try_map9,
+    Fn9Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9]
+);
+define_typed_map!(
+    map10,
+    try_map10,
+    Fn10Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10]
+);
+define_typed_map!(
+    map11,
+    try_map11,
+    Fn11Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11]
+);
+define_typed_map!(
+    map12,
+    try_map12,
+    Fn12Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12]
+);
+define_typed_map!(
+    map13,
+    try_map13,
+    Fn13Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13]
+);
+define_typed_map!(
+    map14,
+    try_map14,
+    Fn14Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14]
+);
+define_typed_map!(
+    map15,
+    try_map15,
+    Fn15Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15]
+);
+define_typed_map!(
+    map16,
+    try_map16,
+    Fn16Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16]
+);
+define_typed_map!(
+    map17,
+    try_map17,
+    Fn17Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17]
+);
+define_typed_map!(
+    map18,
+    try_map18,
+    Fn18Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18]
+);
+define_typed_map!(
+    map19,
+    try_map19,
+    Fn19Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19]
+);
+define_typed_map!(
+    map20,
+    try_map20,
+    Fn20Decoder,
+    [_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20],
+    [D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20]
+);
+define_typed_map!(
+    map21,
+    try_map21,
+    Fn21Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21
+    ]
+);
+define_typed_map!(
+    map22,
+    try_map22,
+    Fn22Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22
+    ]
+);
+define_typed_map!(
+    map23,
+    try_map23,
+    Fn23Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23
+    ]
+);
+define_typed_map!(
+    map24,
+    try_map24,
+    Fn24Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24
+    ]
+);
+define_typed_map!(
+    map25,
+    try_map25,
+    Fn25Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25
+    ]
+);
+define_typed_map!(
+    map26,
+    try_map26,
+    Fn26Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26
+    ]
+);
+define_typed_map!(
+    map27,
+    try_map27,
+    Fn27Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27
+    ]
+);
+define_typed_map!(
+    map28,
+    try_map28,
+    Fn28Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28
+    ]
+);
+define_typed_map!(
+    map29,
+    try_map29,
+    Fn29Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29
+    ]
+);
+define_typed_map!(
+    map30,
+    try_map30,
+    Fn30Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30
+    ]
+);
+define_typed_map!(
+    map31,
+    try_map31,
+    Fn31Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31
+    ]
+);
+define_typed_map!(
+    map32,
+    try_map32,
+    Fn32Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32
+    ]
+);
+define_typed_map!(
+    map33,
+    try_map33,
+    Fn33Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33
+    ]
+);
+define_typed_map!(
+    map34,
+    try_map34,
+    Fn34Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34
+    ]
+);
+define_typed_map!(
+    map35,
+    try_map35,
+    Fn35Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35
+    ]
+);
+define_typed_map!(
+    map36,
+    try_map36,
+    Fn36Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36
+    ]
+);
+define_typed_map!(
+    map37,
+    try_map37,
+    Fn37Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37
+    ]
+);
+define_typed_map!(
+    map38,
+    try_map38,
+    Fn38Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38
+    ]
+);
+define_typed_map!(
+    map39,
+    try_map39,
+    Fn39Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39
+    ]
+);
+define_typed_map!(
+    map40,
+    try_map40,
+    Fn40Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40
+    ]
+);
+define_typed_map!(
+    map41,
+    try_map41,
+    Fn41Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41
+    ]
+);
+define_typed_map!(
+    map42,
+    try_map42,
+    Fn42Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42
+    ]
+);
+define_typed_map!(
+    map43,
+    try_map43,
+    Fn43Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43
+    ]
+);
+define_typed_map!(
+    map44,
+    try_map44,
+    Fn44Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44
+    ]
+);
+define_typed_map!(
+    map45,
+    try_map45,
+    Fn45Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45
+    ]
+);
+define_typed_map!(
+    map46,
+    try_map46,
+    Fn46Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46
+    ]
+);
+define_typed_map!(
+    map47,
+    try_map47,
+    Fn47Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46, _47
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46, D47
+    ]
+);
+define_typed_map!(
+    map48,
+    try_map48,
+    Fn48Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46, _47, _48
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46, D47, D48
+    ]
+);
+define_typed_map!(
+    map49,
+    try_map49,
+    Fn49Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46, _47, _48, _49
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46, D47, D48, D49
+    ]
+);
+define_typed_map!(
+    map50,
+    try_map50,
+    Fn50Decoder,
+    [
+        _1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12, _13, _14, _15, _16, _17, _18, _19, _20,
+        _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31, _32, _33, _34, _35, _36, _37, _38,
+        _39, _40, _41, _42, _43, _44, _45, _46, _47, _48, _49, _50
+    ],
+    [
+        D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15, D16, D17, D18, D19, D20,
+        D21, D22, D23, D24, D25, D26, D27, D28, D29, D30, D31, D32, D33, D34, D35, D36, D37, D38,
+        D39, D40, D41, D42, D43, D44, D45, D46, D47, D48, D49, D50
+    ]
+);